@@ -0,0 +1,299 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use rand::seq::SliceRandom;
+
+use super::peer_discovery::PeerDiscovery;
+use crate::shared::crypto::hash_data;
+
+/// Content-addressed identifier for a gossip message (a SHA-256 digest).
+pub type MessageHash = [u8; 32];
+
+/// Default number of peers a message is forwarded to on each round.
+const DEFAULT_FANOUT: usize = 6;
+
+/// Default interval between periodic rally ticks.
+const DEFAULT_RALLY_INTERVAL: Duration = Duration::from_millis(2_500);
+
+/// A message propagated through the gossip overlay.
+#[derive(Debug, Clone)]
+pub struct GossipMessage {
+    /// Content hash used to deduplicate the message across the network.
+    pub hash: MessageHash,
+    /// Topic the message belongs to.
+    pub topic: String,
+    /// Capability a peer must advertise to be a valid relay target.
+    pub capability: String,
+    /// Remaining hop budget; the message is dropped once it reaches zero.
+    pub ttl: u8,
+    /// Opaque message body.
+    pub payload: Vec<u8>,
+}
+
+impl GossipMessage {
+    /// Builds a message for `topic`/`capability`, deriving its content hash
+    /// from the payload.
+    pub fn new(topic: impl Into<String>, capability: impl Into<String>, ttl: u8, payload: Vec<u8>) -> Self {
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&hash_data(&payload));
+        Self {
+            hash,
+            topic: topic.into(),
+            capability: capability.into(),
+            ttl,
+            payload,
+        }
+    }
+}
+
+/// Disposition returned by a [`MessageValidator`] for an incoming message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationResult {
+    /// Relay the message but take no local action.
+    Keep,
+    /// Drop the message without relaying (e.g. spam or malformed).
+    Discard,
+    /// Process locally and relay onward.
+    ProcessAndKeep,
+}
+
+/// Decides whether a gossip message should be relayed, processed, or dropped.
+pub trait MessageValidator: Send + Sync {
+    /// Validates a message before the engine relays it.
+    fn validate(&self, msg: &GossipMessage) -> ValidationResult;
+}
+
+/// Carries gossip messages to peers and delivers locally-accepted ones.
+///
+/// The engine selects *which* peers to reach; the transport owns the actual
+/// send path (socket, channel, test double). This keeps dissemination policy
+/// independent of the wire and gives [`ValidationResult::ProcessAndKeep`] a
+/// concrete effect via [`deliver_local`](Self::deliver_local).
+pub trait GossipTransport: Send + Sync {
+    /// Relays `msg` toward the peer identified by `peer_id`.
+    fn relay(&self, peer_id: &str, msg: &GossipMessage);
+
+    /// Hands `msg` to the local node for processing. Invoked only for messages
+    /// the validator returns [`ValidationResult::ProcessAndKeep`] for; the
+    /// default is a no-op for nodes that merely relay.
+    fn deliver_local(&self, _msg: &GossipMessage) {}
+}
+
+/// Book-keeping for a message the engine is still actively re-broadcasting.
+struct Pending {
+    msg: GossipMessage,
+    forwarded: HashSet<String>,
+}
+
+/// Epidemic broadcast engine layered over [`PeerDiscovery`].
+///
+/// New messages are pushed to a random fanout subset of capability-matching
+/// peers immediately; a periodic rally tick re-broadcasts live messages to
+/// peers that have not yet received them, and a seen-set suppresses the
+/// duplicates that epidemic dissemination inevitably produces.
+pub struct GossipEngine {
+    discovery: Arc<PeerDiscovery>,
+    topic: String,
+    validator: Arc<dyn MessageValidator>,
+    transport: Arc<dyn GossipTransport>,
+    fanout: usize,
+    rally_interval: Duration,
+    seen: Arc<RwLock<HashMap<MessageHash, Instant>>>,
+    seen_ttl: Duration,
+    pending: Arc<RwLock<HashMap<MessageHash, Pending>>>,
+}
+
+impl GossipEngine {
+    /// Creates a new gossip engine for `topic` over the given discovery table,
+    /// relaying selected messages through `transport`.
+    pub fn new(
+        discovery: Arc<PeerDiscovery>,
+        topic: String,
+        validator: Arc<dyn MessageValidator>,
+        transport: Arc<dyn GossipTransport>,
+    ) -> Self {
+        Self {
+            discovery,
+            topic,
+            validator,
+            transport,
+            fanout: DEFAULT_FANOUT,
+            rally_interval: DEFAULT_RALLY_INTERVAL,
+            seen: Arc::new(RwLock::new(HashMap::new())),
+            seen_ttl: Duration::from_secs(60),
+            pending: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Overrides the per-round fanout.
+    pub fn with_fanout(mut self, fanout: usize) -> Self {
+        self.fanout = fanout;
+        self
+    }
+
+    /// The interval callers should use between [`rally_tick`](Self::rally_tick) calls.
+    pub fn rally_interval(&self) -> Duration {
+        self.rally_interval
+    }
+
+    /// The topic this engine gossips on.
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// Validates and broadcasts a message to a random fanout of peers.
+    ///
+    /// Each selected peer is handed to the transport's relay path, and a
+    /// [`ValidationResult::ProcessAndKeep`] message is additionally delivered
+    /// locally. Returns the peer ids the message was forwarded to. A message
+    /// the validator discards, or one already seen, yields an empty list.
+    pub fn broadcast(&self, msg: GossipMessage) -> Vec<String> {
+        let disposition = self.validator.validate(&msg);
+        if disposition == ValidationResult::Discard {
+            return Vec::new();
+        }
+        if self.mark_seen(msg.hash) {
+            return Vec::new();
+        }
+
+        if disposition == ValidationResult::ProcessAndKeep {
+            self.transport.deliver_local(&msg);
+        }
+
+        let targets = self.pick_targets(&msg.capability, &HashSet::new());
+        for id in &targets {
+            self.transport.relay(id, &msg);
+        }
+
+        let mut pending = self.pending.write().unwrap();
+        let entry = pending.entry(msg.hash).or_insert_with(|| Pending {
+            msg: msg.clone(),
+            forwarded: HashSet::new(),
+        });
+        for id in &targets {
+            entry.forwarded.insert(id.clone());
+        }
+        targets
+    }
+
+    /// Re-broadcasts live messages to peers that have not yet received them and
+    /// expires stale seen-set entries. Intended to be driven every
+    /// [`rally_interval`](Self::rally_interval).
+    pub fn rally_tick(&self) {
+        self.expire_seen();
+
+        let mut pending = self.pending.write().unwrap();
+        let mut exhausted = Vec::new();
+        for (hash, entry) in pending.iter_mut() {
+            if entry.msg.ttl == 0 {
+                exhausted.push(*hash);
+                continue;
+            }
+            let targets = self.pick_targets(&entry.msg.capability, &entry.forwarded);
+            for id in targets {
+                self.transport.relay(&id, &entry.msg);
+                entry.forwarded.insert(id);
+            }
+            entry.msg.ttl -= 1;
+        }
+        for hash in exhausted {
+            pending.remove(&hash);
+        }
+    }
+
+    /// Number of messages currently being re-broadcast.
+    pub fn pending_count(&self) -> usize {
+        self.pending.read().unwrap().len()
+    }
+
+    /// Chooses up to `fanout` capability-matching peers, excluding `exclude`.
+    fn pick_targets(&self, capability: &str, exclude: &HashSet<String>) -> Vec<String> {
+        let candidates: Vec<String> = self
+            .discovery
+            .get_peers_by_capability(capability)
+            .into_iter()
+            .map(|p| p.peer_id)
+            .filter(|id| !exclude.contains(id))
+            .collect();
+        let mut rng = rand::thread_rng();
+        candidates
+            .choose_multiple(&mut rng, self.fanout)
+            .cloned()
+            .collect()
+    }
+
+    /// Records a message hash as seen; returns `true` if it was already present.
+    fn mark_seen(&self, hash: MessageHash) -> bool {
+        let mut seen = self.seen.write().unwrap();
+        if seen.contains_key(&hash) {
+            return true;
+        }
+        seen.insert(hash, Instant::now());
+        false
+    }
+
+    /// Drops seen-set entries older than the configured expiry.
+    fn expire_seen(&self) {
+        let now = Instant::now();
+        let mut seen = self.seen.write().unwrap();
+        seen.retain(|_, t| now.duration_since(*t) < self.seen_ttl);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::peer_discovery::DiscoveryConfig;
+
+    struct AcceptAll;
+    impl MessageValidator for AcceptAll {
+        fn validate(&self, _msg: &GossipMessage) -> ValidationResult {
+            ValidationResult::ProcessAndKeep
+        }
+    }
+
+    /// Transport that records relayed peer ids and locally-delivered messages.
+    #[derive(Default)]
+    struct RecordingTransport {
+        relayed: RwLock<Vec<String>>,
+        delivered: RwLock<Vec<MessageHash>>,
+    }
+
+    impl GossipTransport for RecordingTransport {
+        fn relay(&self, peer_id: &str, _msg: &GossipMessage) {
+            self.relayed.write().unwrap().push(peer_id.to_string());
+        }
+
+        fn deliver_local(&self, msg: &GossipMessage) {
+            self.delivered.write().unwrap().push(msg.hash);
+        }
+    }
+
+    #[test]
+    fn test_duplicate_broadcast_suppressed() {
+        let discovery = Arc::new(PeerDiscovery::new(DiscoveryConfig::default()));
+        let transport = Arc::new(RecordingTransport::default());
+        let engine = GossipEngine::new(discovery, "t".to_string(), Arc::new(AcceptAll), transport);
+        let msg = GossipMessage::new("t", "worker", 3, b"hello".to_vec());
+        engine.broadcast(msg.clone());
+        // Re-broadcasting the same content hash is suppressed by the seen-set.
+        assert!(engine.broadcast(msg).is_empty());
+    }
+
+    #[test]
+    fn test_process_and_keep_delivers_locally() {
+        let discovery = Arc::new(PeerDiscovery::new(DiscoveryConfig::default()));
+        let transport = Arc::new(RecordingTransport::default());
+        let engine = GossipEngine::new(
+            discovery,
+            "t".to_string(),
+            Arc::new(AcceptAll),
+            Arc::clone(&transport) as Arc<dyn GossipTransport>,
+        );
+        let msg = GossipMessage::new("t", "worker", 3, b"hello".to_vec());
+        engine.broadcast(msg.clone());
+        // A ProcessAndKeep message is handed to the local delivery path.
+        assert_eq!(transport.delivered.read().unwrap().as_slice(), &[msg.hash]);
+    }
+}
@@ -3,5 +3,6 @@
 pub mod peer_discovery;
 pub mod network_topology;
 pub mod service_registry;
+pub mod gossip;
 
 pub use service_registry::ServiceRegistry;
\ No newline at end of file
@@ -1,8 +1,129 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::net::SocketAddr;
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
+use crate::shared::crypto::hash_data;
+use crate::validator::reputation_system::{ReputationEvent, ReputationSystem};
+
+/// Action taken against a misbehaving peer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Punishment {
+    /// No action required.
+    None,
+    /// Drop the current connection but allow re-admission.
+    Disconnect,
+    /// Remove the peer and refuse re-admission until the duration elapses.
+    Ban(Duration),
+}
+
+/// Width of a Kademlia node identifier in bytes (256 bits, one SHA-256 digest).
+const ID_BYTES: usize = 32;
+
+/// Default k-bucket capacity (the Kademlia system-wide replication parameter).
+const DEFAULT_K: usize = 20;
+
+/// 256-bit node identifier used for XOR-distance routing.
+pub type NodeId = [u8; ID_BYTES];
+
+/// Derives a node id from a peer id by hashing it with SHA-256.
+fn node_id_from(peer_id: &str) -> NodeId {
+    let digest = hash_data(peer_id.as_bytes());
+    let mut id = [0u8; ID_BYTES];
+    id.copy_from_slice(&digest);
+    id
+}
+
+/// Returns the index of the k-bucket a peer belongs to, i.e. the position of
+/// the highest set bit of `local XOR other` counted from the least significant
+/// bit. `None` when the two ids are identical (distance zero).
+fn bucket_index(local: &NodeId, other: &NodeId) -> Option<usize> {
+    for byte in 0..ID_BYTES {
+        let diff = local[byte] ^ other[byte];
+        if diff != 0 {
+            let bit_from_msb = byte * 8 + diff.leading_zeros() as usize;
+            return Some(ID_BYTES * 8 - 1 - bit_from_msb);
+        }
+    }
+    None
+}
+
+/// Compares two ids by XOR distance to `target` (closer sorts first).
+fn closer_to(target: &NodeId, a: &NodeId, b: &NodeId) -> std::cmp::Ordering {
+    for i in 0..ID_BYTES {
+        let da = target[i] ^ a[i];
+        let db = target[i] ^ b[i];
+        if da != db {
+            return da.cmp(&db);
+        }
+    }
+    std::cmp::Ordering::Equal
+}
+
+/// XOR-distance routing table of k-buckets.
+///
+/// Each bucket keeps its peers ordered oldest-first; a peer that is seen again
+/// is moved to the back, so when a full bucket is offered a new peer we keep
+/// the oldest live node (front) and drop the newcomer — Kademlia's LRU policy
+/// that favours long-lived, well-behaved contacts.
+struct RoutingTable {
+    local_id: NodeId,
+    k: usize,
+    buckets: Vec<VecDeque<String>>,
+}
+
+impl RoutingTable {
+    fn new(local_id: NodeId, k: usize) -> Self {
+        Self {
+            local_id,
+            k,
+            buckets: (0..ID_BYTES * 8).map(|_| VecDeque::new()).collect(),
+        }
+    }
+
+    fn add(&mut self, peer_id: &str) {
+        let idx = match bucket_index(&self.local_id, &node_id_from(peer_id)) {
+            Some(idx) => idx,
+            None => return,
+        };
+        let bucket = &mut self.buckets[idx];
+        if let Some(pos) = bucket.iter().position(|p| p == peer_id) {
+            let existing = bucket.remove(pos).unwrap();
+            bucket.push_back(existing);
+        } else if bucket.len() < self.k {
+            bucket.push_back(peer_id.to_string());
+        }
+        // Bucket full: keep the oldest live node, drop the newcomer.
+    }
+
+    fn remove(&mut self, peer_id: &str) {
+        if let Some(idx) = bucket_index(&self.local_id, &node_id_from(peer_id)) {
+            self.buckets[idx].retain(|p| p != peer_id);
+        }
+    }
+
+    /// Marks a peer as freshly seen, moving it to the back of its bucket.
+    fn touch(&mut self, peer_id: &str) {
+        self.add(peer_id);
+    }
+
+    /// Returns up to `n` peer ids closest to `target` by XOR distance.
+    ///
+    /// Each id's [`NodeId`] is hashed once up front so the sort comparator only
+    /// compares precomputed ids, keeping this to O(n) hashes rather than the
+    /// O(n log n) a hash-in-comparator sort would incur.
+    fn closest(&self, target: &NodeId, n: usize) -> Vec<String> {
+        let mut ids: Vec<(String, NodeId)> = self
+            .buckets
+            .iter()
+            .flatten()
+            .map(|id| (id.clone(), node_id_from(id)))
+            .collect();
+        ids.sort_by(|a, b| closer_to(target, &a.1, &b.1));
+        ids.into_iter().take(n).map(|(id, _)| id).collect()
+    }
+}
+
 /// Information about a discovered peer
 #[derive(Debug, Clone)]
 pub struct PeerInfo {
@@ -39,19 +160,41 @@ pub struct PeerDiscovery {
     known_peers: Arc<RwLock<HashMap<String, PeerInfo>>>,
     active_discoveries: Arc<RwLock<HashSet<DiscoveryProtocol>>>,
     discovery_enabled: Arc<RwLock<bool>>,
+    routing_table: Arc<RwLock<RoutingTable>>,
+    reputation: Arc<ReputationSystem>,
+    min_admission_score: f64,
+    ban_duration: Duration,
+    banned: Arc<RwLock<HashMap<String, Instant>>>,
 }
 
 impl PeerDiscovery {
     /// Creates a new PeerDiscovery instance
     pub fn new(config: DiscoveryConfig) -> Self {
+        let local_id = node_id_from(&uuid::Uuid::new_v4().to_string());
         Self {
             config,
             known_peers: Arc::new(RwLock::new(HashMap::new())),
             active_discoveries: Arc::new(RwLock::new(HashSet::new())),
             discovery_enabled: Arc::new(RwLock::new(false)),
+            routing_table: Arc::new(RwLock::new(RoutingTable::new(local_id, DEFAULT_K))),
+            reputation: Arc::new(ReputationSystem::new(50.0, 0.0, 100.0)),
+            min_admission_score: 10.0,
+            ban_duration: Duration::from_secs(3600),
+            banned: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
+    /// Uses a shared reputation system and sets the minimum admission score.
+    pub fn with_reputation(
+        mut self,
+        reputation: Arc<ReputationSystem>,
+        min_admission_score: f64,
+    ) -> Self {
+        self.reputation = reputation;
+        self.min_admission_score = min_admission_score;
+        self
+    }
+
     /// Starts peer discovery process
     pub fn start_discovery(&self) -> Result<(), String> {
         let mut enabled = self.discovery_enabled.write().unwrap();
@@ -86,13 +229,24 @@ impl PeerDiscovery {
         
         active.insert(protocol.clone());
         
-        // Stub implementation - in reality would start protocol-specific discovery
         match protocol {
             DiscoveryProtocol::Bootstrap => self.discover_from_bootstrap(),
+            DiscoveryProtocol::DHT => self.bootstrap_dht(),
             _ => Ok(()),
         }
     }
 
+    /// Seeds the routing table with every peer already known, bringing the DHT
+    /// online from whatever contacts bootstrap/manual discovery has gathered.
+    fn bootstrap_dht(&self) -> Result<(), String> {
+        let peers = self.known_peers.read().unwrap();
+        let mut table = self.routing_table.write().unwrap();
+        for peer_id in peers.keys() {
+            table.add(peer_id);
+        }
+        Ok(())
+    }
+
     /// Discovers peers from bootstrap nodes
     fn discover_from_bootstrap(&self) -> Result<(), String> {
         for (i, addr) in self.config.bootstrap_nodes.iter().enumerate() {
@@ -110,14 +264,29 @@ impl PeerDiscovery {
     }
 
     /// Adds a discovered peer
+    ///
+    /// Peers that are currently banned, or whose reputation has fallen below
+    /// `min_admission_score`, are refused so misbehaviour gates connectivity.
     pub fn add_peer(&self, peer: PeerInfo) -> Result<(), String> {
+        if self.is_banned(&peer.peer_id) {
+            return Err("Peer is banned".to_string());
+        }
+        if let Some(score) = self.reputation.get_reputation(&peer.peer_id) {
+            if score.score < self.min_admission_score {
+                return Err("Peer reputation below admission threshold".to_string());
+            }
+        }
+
         let mut peers = self.known_peers.write().unwrap();
-        
+
         if peers.len() >= self.config.max_peers {
             return Err("Maximum peer limit reached".to_string());
         }
-        
-        peers.insert(peer.peer_id.clone(), peer);
+
+        let peer_id = peer.peer_id.clone();
+        peers.insert(peer_id.clone(), peer);
+        drop(peers);
+        self.routing_table.write().unwrap().add(&peer_id);
         Ok(())
     }
 
@@ -127,6 +296,8 @@ impl PeerDiscovery {
         if peers.remove(peer_id).is_none() {
             return Err("Peer not found".to_string());
         }
+        drop(peers);
+        self.routing_table.write().unwrap().remove(peer_id);
         Ok(())
     }
 
@@ -137,12 +308,37 @@ impl PeerDiscovery {
             Some(peer) => {
                 peer.last_seen = Instant::now();
                 peer.latency_ms = Some(latency_ms);
+                drop(peers);
+                self.routing_table.write().unwrap().touch(peer_id);
                 Ok(())
             }
             None => Err("Peer not found".to_string()),
         }
     }
 
+    /// Returns the `k` closest known peers to `target_id`, sorted by XOR
+    /// distance — the structured-lookup counterpart to the full-map scan in
+    /// [`get_peers_by_capability`](Self::get_peers_by_capability).
+    pub fn find_node(&self, target_id: &NodeId) -> Vec<PeerInfo> {
+        let ids = self.routing_table.read().unwrap().closest(target_id, DEFAULT_K);
+        self.peers_for_ids(&ids)
+    }
+
+    /// Returns the `n` peers closest to this node's own id.
+    pub fn closest_peers(&self, n: usize) -> Vec<PeerInfo> {
+        let ids = {
+            let table = self.routing_table.read().unwrap();
+            table.closest(&table.local_id, n)
+        };
+        self.peers_for_ids(&ids)
+    }
+
+    /// Resolves routing-table ids back into full `PeerInfo` records.
+    fn peers_for_ids(&self, ids: &[String]) -> Vec<PeerInfo> {
+        let peers = self.known_peers.read().unwrap();
+        ids.iter().filter_map(|id| peers.get(id).cloned()).collect()
+    }
+
     /// Gets all known peers
     pub fn get_peers(&self) -> Vec<PeerInfo> {
         let peers = self.known_peers.read().unwrap();
@@ -159,14 +355,87 @@ impl PeerDiscovery {
             .collect()
     }
 
+    /// Reports an observed reputation event for a peer, punishing it per policy.
+    ///
+    /// The peer's score is updated (registering it first if necessary); a
+    /// `MaliciousBehavior` event, or a score that drops below
+    /// `min_admission_score`, maps to a `Ban` which removes the peer and records a
+    /// ban-until timestamp honoured by future admission and cleanup.
+    pub fn report_peer(&self, peer_id: &str, event: ReputationEvent) -> Result<(), String> {
+        if self.reputation.get_reputation(peer_id).is_none() {
+            let _ = self.reputation.register_validator(peer_id.to_string());
+        }
+        let is_malicious = matches!(event, ReputationEvent::MaliciousBehavior);
+        self.reputation.update_reputation(peer_id, event)?;
+
+        let score = self
+            .reputation
+            .get_reputation(peer_id)
+            .map(|s| s.score)
+            .unwrap_or(self.min_admission_score);
+
+        let punishment = if is_malicious || score < self.min_admission_score {
+            Punishment::Ban(self.ban_duration)
+        } else {
+            Punishment::None
+        };
+
+        if let Punishment::Ban(duration) = punishment {
+            self.banned
+                .write()
+                .unwrap()
+                .insert(peer_id.to_string(), Instant::now() + duration);
+            let _ = self.remove_peer(peer_id);
+        }
+        Ok(())
+    }
+
+    /// Returns the currently banned peers with their ban-until timestamps.
+    pub fn banned_peers(&self) -> Vec<(String, Instant)> {
+        let now = Instant::now();
+        self.banned
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(_, until)| **until > now)
+            .map(|(id, until)| (id.clone(), *until))
+            .collect()
+    }
+
+    /// Whether a peer's ban is still in force.
+    fn is_banned(&self, peer_id: &str) -> bool {
+        match self.banned.read().unwrap().get(peer_id) {
+            Some(until) => *until > Instant::now(),
+            None => false,
+        }
+    }
+
     /// Removes stale peers
     pub fn cleanup_stale_peers(&self) {
-        let mut peers = self.known_peers.write().unwrap();
         let now = Instant::now();
-        
-        peers.retain(|_, peer| {
-            now.duration_since(peer.last_seen) < self.config.peer_timeout
-        });
+        let stale: Vec<String> = {
+            let mut peers = self.known_peers.write().unwrap();
+            let stale: Vec<String> = peers
+                .iter()
+                .filter(|(_, peer)| now.duration_since(peer.last_seen) >= self.config.peer_timeout)
+                .map(|(id, _)| id.clone())
+                .collect();
+            for id in &stale {
+                peers.remove(id);
+            }
+            stale
+        };
+        // Evict the same peers from the routing table so stale ids don't
+        // accumulate in the k-buckets (previously they were only filtered out
+        // lazily at read time).
+        if !stale.is_empty() {
+            let mut table = self.routing_table.write().unwrap();
+            for id in &stale {
+                table.remove(id);
+            }
+        }
+        // Drop bans that have expired so peers can be re-admitted.
+        self.banned.write().unwrap().retain(|_, until| *until > now);
     }
 
     /// Gets the number of active peers
@@ -204,4 +473,50 @@ mod tests {
         assert_eq!(discovery.peer_count(), 0);
         assert!(!discovery.is_discovering());
     }
+
+    #[test]
+    fn test_find_node_returns_closest_peers() {
+        let discovery = PeerDiscovery::new(DiscoveryConfig::default());
+        for i in 0..5 {
+            let peer = PeerInfo {
+                peer_id: format!("peer-{}", i),
+                address: "127.0.0.1:8000".parse().unwrap(),
+                capabilities: vec![],
+                last_seen: Instant::now(),
+                latency_ms: None,
+                version: "1.0.0".to_string(),
+            };
+            discovery.add_peer(peer).unwrap();
+        }
+        let target = node_id_from("peer-0");
+        let closest = discovery.find_node(&target);
+        assert_eq!(closest.first().map(|p| p.peer_id.as_str()), Some("peer-0"));
+        assert_eq!(closest.len(), 5);
+    }
+
+    #[test]
+    fn test_cleanup_prunes_routing_table() {
+        let config = DiscoveryConfig {
+            peer_timeout: Duration::from_millis(1),
+            ..DiscoveryConfig::default()
+        };
+        let discovery = PeerDiscovery::new(config);
+        let peer = PeerInfo {
+            peer_id: "peer-x".to_string(),
+            address: "127.0.0.1:8000".parse().unwrap(),
+            capabilities: vec![],
+            last_seen: Instant::now(),
+            latency_ms: None,
+            version: "1.0.0".to_string(),
+        };
+        discovery.add_peer(peer).unwrap();
+
+        std::thread::sleep(Duration::from_millis(5));
+        discovery.cleanup_stale_peers();
+
+        // The stale id is gone from the k-buckets, not just from known_peers.
+        let table = discovery.routing_table.read().unwrap();
+        let ids = table.closest(&node_id_from("peer-x"), 10);
+        assert!(!ids.contains(&"peer-x".to_string()));
+    }
 }
\ No newline at end of file
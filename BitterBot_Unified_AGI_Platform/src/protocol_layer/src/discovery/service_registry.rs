@@ -1,7 +1,12 @@
 use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
+use super::peer_discovery::{PeerDiscovery, PeerInfo};
+use crate::worker::health_reporter::{HealthReporter, MemberState};
+
 /// Service metadata
 #[derive(Debug, Clone)]
 pub struct ServiceInfo {
@@ -246,6 +251,197 @@ impl Default for ServiceRegistry {
     }
 }
 
+/// A pluggable backend that external infrastructure (an in-memory catalog, a
+/// Consul/DNS-style service catalog, ...) can implement to supply and receive
+/// service locations.
+pub trait ServiceRegistryBackend: Send + Sync {
+    /// Registers a service instance at `addr` with the given tags.
+    fn register(&self, service: &str, addr: SocketAddr, tags: Vec<String>) -> Result<(), String>;
+
+    /// Resolves the current addresses advertised for a service.
+    fn resolve(&self, service: &str) -> Result<Vec<SocketAddr>, String>;
+
+    /// Resolves addresses together with their tags.
+    ///
+    /// Defaults to [`resolve`](Self::resolve) with empty tags; backends that
+    /// retain tags should override this so capabilities survive resolution.
+    fn resolve_entries(&self, service: &str) -> Result<Vec<(SocketAddr, Vec<String>)>, String> {
+        Ok(self
+            .resolve(service)?
+            .into_iter()
+            .map(|addr| (addr, Vec::new()))
+            .collect())
+    }
+
+    /// Returns a receiver that is notified whenever the service's address set
+    /// changes.
+    fn watch(&self, service: &str) -> Receiver<Vec<SocketAddr>>;
+}
+
+/// A resolved service instance.
+#[derive(Debug, Clone)]
+struct CatalogEntry {
+    addr: SocketAddr,
+    tags: Vec<String>,
+}
+
+/// In-process [`ServiceRegistryBackend`] useful for tests and single-node setups.
+pub struct InMemoryServiceRegistry {
+    catalog: Arc<RwLock<HashMap<String, Vec<CatalogEntry>>>>,
+    watchers: Arc<RwLock<HashMap<String, Vec<Sender<Vec<SocketAddr>>>>>>,
+}
+
+impl InMemoryServiceRegistry {
+    /// Creates an empty in-memory registry.
+    pub fn new() -> Self {
+        Self {
+            catalog: Arc::new(RwLock::new(HashMap::new())),
+            watchers: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Notifies watchers of a service with its current address set, dropping
+    /// receivers that have been closed.
+    fn notify(&self, service: &str, addrs: &[SocketAddr]) {
+        let mut watchers = self.watchers.write().unwrap();
+        if let Some(senders) = watchers.get_mut(service) {
+            senders.retain(|tx| tx.send(addrs.to_vec()).is_ok());
+        }
+    }
+}
+
+impl Default for InMemoryServiceRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServiceRegistryBackend for InMemoryServiceRegistry {
+    fn register(&self, service: &str, addr: SocketAddr, tags: Vec<String>) -> Result<(), String> {
+        let addrs = {
+            let mut catalog = self.catalog.write().unwrap();
+            let entries = catalog.entry(service.to_string()).or_default();
+            match entries.iter_mut().find(|e| e.addr == addr) {
+                Some(existing) => existing.tags = tags,
+                None => entries.push(CatalogEntry { addr, tags }),
+            }
+            entries.iter().map(|e| e.addr).collect::<Vec<_>>()
+        };
+        self.notify(service, &addrs);
+        Ok(())
+    }
+
+    fn resolve(&self, service: &str) -> Result<Vec<SocketAddr>, String> {
+        let catalog = self.catalog.read().unwrap();
+        match catalog.get(service) {
+            Some(entries) => Ok(entries.iter().map(|e| e.addr).collect()),
+            None => Err("Service not found".to_string()),
+        }
+    }
+
+    fn resolve_entries(&self, service: &str) -> Result<Vec<(SocketAddr, Vec<String>)>, String> {
+        let catalog = self.catalog.read().unwrap();
+        match catalog.get(service) {
+            Some(entries) => Ok(entries.iter().map(|e| (e.addr, e.tags.clone())).collect()),
+            None => Err("Service not found".to_string()),
+        }
+    }
+
+    fn watch(&self, service: &str) -> Receiver<Vec<SocketAddr>> {
+        let (tx, rx) = channel();
+        // Prime the watcher with the current snapshot if one exists.
+        if let Ok(addrs) = self.resolve(service) {
+            let _ = tx.send(addrs);
+        }
+        self.watchers
+            .write()
+            .unwrap()
+            .entry(service.to_string())
+            .or_default()
+            .push(tx);
+        rx
+    }
+}
+
+/// Bridges an external service catalog into the local [`PeerDiscovery`] table.
+///
+/// On each sync it resolves the tracked services from the backend, maps every
+/// entry into a [`PeerInfo`] whose capabilities are drawn from the service
+/// tags, and feeds them through `add_peer`. It can also advertise the local
+/// node's `Alive` components back into the catalog; published entries are
+/// never withdrawn, so a component stays listed after it leaves `Alive`. This
+/// is how cluster-membership layers bootstrap from an external catalog.
+pub struct ExternalCatalogSync {
+    backend: Arc<dyn ServiceRegistryBackend>,
+    discovery: Arc<PeerDiscovery>,
+    services: Vec<String>,
+    local_addr: SocketAddr,
+    sync_interval: Duration,
+}
+
+impl ExternalCatalogSync {
+    /// Creates a sync bridge for the given services.
+    pub fn new(
+        backend: Arc<dyn ServiceRegistryBackend>,
+        discovery: Arc<PeerDiscovery>,
+        services: Vec<String>,
+        local_addr: SocketAddr,
+        sync_interval: Duration,
+    ) -> Self {
+        Self {
+            backend,
+            discovery,
+            services,
+            local_addr,
+            sync_interval,
+        }
+    }
+
+    /// The interval callers should wait between [`sync_once`](Self::sync_once) calls.
+    pub fn sync_interval(&self) -> Duration {
+        self.sync_interval
+    }
+
+    /// Resolves every tracked service and feeds the discovered instances into
+    /// peer discovery. Returns the number of peers admitted.
+    pub fn sync_once(&self) -> usize {
+        let mut admitted = 0;
+        for service in &self.services {
+            let entries = match self.backend.resolve_entries(service) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+            for (addr, tags) in entries {
+                let peer = PeerInfo {
+                    peer_id: format!("{}@{}", service, addr),
+                    address: addr,
+                    capabilities: tags,
+                    last_seen: Instant::now(),
+                    latency_ms: None,
+                    version: "0.0.0".to_string(),
+                };
+                if self.discovery.add_peer(peer).is_ok() {
+                    admitted += 1;
+                }
+            }
+        }
+        admitted
+    }
+
+    /// Publishes the local node's currently-alive components into the catalog,
+    /// tagged `healthy`. Members that are not `Alive` are skipped; entries
+    /// published by an earlier call are not withdrawn.
+    pub fn advertise_health(&self, reporter: &HealthReporter) {
+        for member in reporter.membership_report() {
+            if member.state == MemberState::Alive {
+                let _ = self
+                    .backend
+                    .register(&member.name, self.local_addr, vec!["healthy".to_string()]);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -12,6 +12,7 @@ pub mod validator;
 pub mod worker;
 pub mod discovery;
 pub mod shared;
+pub mod wire;
 
 // Re-export commonly used types
 pub use shared::{
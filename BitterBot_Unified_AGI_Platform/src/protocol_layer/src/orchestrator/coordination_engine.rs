@@ -1,41 +1,345 @@
 //! Coordination engine for distributed task management
 
-use std::sync::Arc;
-use tokio::sync::RwLock;
-use crate::shared::{Task, TaskResult, WorkerInfo, Result};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle, Thread};
+use std::time::Duration;
+
+use crossbeam_deque::{Injector, Stealer, Worker as LocalQueue};
+
+use crate::shared::{Result, Task, TaskMetrics, TaskResult};
+
+/// How long an idle worker parks before re-checking for work or shutdown.
+const PARK_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// A unit of schedulable work: a task plus the channel its result returns on.
+struct Job {
+    task: Task,
+    respond: Sender<TaskResult>,
+}
+
+/// Closure that executes a task body and produces its result. Injected so the
+/// scheduler stays independent of how a task is actually run.
+pub type TaskHandler = Arc<dyn Fn(Task) -> TaskResult + Send + Sync>;
+
+/// Handle to a spawned worker thread, retained so it can be unparked on new
+/// work and joined on shutdown.
+struct WorkerThread {
+    handle: JoinHandle<()>,
+    thread: Thread,
+}
+
+/// Work-stealing scheduler: one global injector feeds per-worker local deques.
+///
+/// Each worker pops from its own deque LIFO, falls back to stealing a batch
+/// from the global injector, and finally steals from sibling workers' stealers
+/// when both are empty. This replaces lock-contended single-queue dispatch and
+/// scales to many short tasks.
+pub struct WorkStealingScheduler {
+    injector: Arc<Injector<Job>>,
+    stealers: Arc<Mutex<Vec<Stealer<Job>>>>,
+    workers: Mutex<Vec<WorkerThread>>,
+    parked: Arc<AtomicUsize>,
+    shutdown: Arc<AtomicBool>,
+    handler: TaskHandler,
+    max_workers: usize,
+}
+
+impl WorkStealingScheduler {
+    /// Creates a scheduler with `initial_workers` worker threads running
+    /// `handler` on each dequeued task. The pool is capped at a small multiple
+    /// of the available parallelism so dynamic scaling cannot exhaust threads.
+    pub fn new(handler: TaskHandler, initial_workers: usize) -> Self {
+        let initial_workers = initial_workers.max(1);
+        let max_workers = default_worker_count()
+            .saturating_mul(4)
+            .max(initial_workers);
+        let scheduler = Self {
+            injector: Arc::new(Injector::new()),
+            stealers: Arc::new(Mutex::new(Vec::new())),
+            workers: Mutex::new(Vec::new()),
+            parked: Arc::new(AtomicUsize::new(0)),
+            shutdown: Arc::new(AtomicBool::new(false)),
+            handler,
+            max_workers,
+        };
+        for _ in 0..initial_workers {
+            scheduler.spawn_worker();
+        }
+        scheduler
+    }
+
+    /// Upper bound on the worker pool.
+    pub fn max_workers(&self) -> usize {
+        self.max_workers
+    }
+
+    /// Pushes a task onto the global injector and wakes a parked worker,
+    /// returning the channel its result will arrive on.
+    pub fn submit(&self, task: Task) -> Receiver<TaskResult> {
+        let (respond, rx) = mpsc::channel();
+        self.injector.push(Job { task, respond });
+        self.wake_one();
+        rx
+    }
+
+    /// Number of tasks waiting in the global injector.
+    pub fn backlog(&self) -> usize {
+        self.injector.len()
+    }
+
+    /// Number of worker threads currently running.
+    pub fn worker_count(&self) -> usize {
+        self.workers.lock().unwrap().len()
+    }
+
+    /// Number of workers currently parked (idle).
+    pub fn parked_workers(&self) -> usize {
+        self.parked.load(Ordering::Acquire)
+    }
+
+    /// Spawns an additional worker, publishing its stealer so siblings can
+    /// steal from it. Returns `false` without spawning once the pool has
+    /// reached [`max_workers`](Self::max_workers).
+    pub fn spawn_worker(&self) -> bool {
+        let mut workers = self.workers.lock().unwrap();
+        if workers.len() >= self.max_workers {
+            return false;
+        }
+
+        let local: LocalQueue<Job> = LocalQueue::new_lifo();
+        self.stealers.lock().unwrap().push(local.stealer());
+
+        let injector = Arc::clone(&self.injector);
+        let stealers = Arc::clone(&self.stealers);
+        let parked = Arc::clone(&self.parked);
+        let shutdown = Arc::clone(&self.shutdown);
+        let handler = Arc::clone(&self.handler);
+
+        let handle = thread::spawn(move || {
+            worker_loop(local, injector, stealers, parked, shutdown, handler);
+        });
+        let thread = handle.thread().clone();
+        workers.push(WorkerThread { handle, thread });
+        true
+    }
+
+    /// Signals all workers to stop and joins them.
+    pub fn shutdown(&self) {
+        self.shutdown.store(true, Ordering::Release);
+        let workers = std::mem::take(&mut *self.workers.lock().unwrap());
+        for worker in &workers {
+            worker.thread.unpark();
+        }
+        for worker in workers {
+            let _ = worker.handle.join();
+        }
+    }
+
+    /// When any worker is parked, unparks the first worker in the pool so a
+    /// freshly pushed job is picked up promptly. The first worker is not
+    /// necessarily a parked one; when it is busy, the job waits until some
+    /// worker next looks for work, at the latest when a parked worker's
+    /// park timeout expires.
+    fn wake_one(&self) {
+        if self.parked.load(Ordering::Acquire) == 0 {
+            return;
+        }
+        if let Some(worker) = self.workers.lock().unwrap().first() {
+            worker.thread.unpark();
+        }
+    }
+}
+
+impl Drop for WorkStealingScheduler {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// The body each worker thread runs: dequeue, execute, park when idle.
+fn worker_loop(
+    local: LocalQueue<Job>,
+    injector: Arc<Injector<Job>>,
+    stealers: Arc<Mutex<Vec<Stealer<Job>>>>,
+    parked: Arc<AtomicUsize>,
+    shutdown: Arc<AtomicBool>,
+    handler: TaskHandler,
+) {
+    while !shutdown.load(Ordering::Acquire) {
+        match find_task(&local, &injector, &stealers) {
+            Some(job) => {
+                let result = handler(job.task);
+                let _ = job.respond.send(result);
+            }
+            None => {
+                parked.fetch_add(1, Ordering::AcqRel);
+                thread::park_timeout(PARK_TIMEOUT);
+                parked.fetch_sub(1, Ordering::AcqRel);
+            }
+        }
+    }
+}
+
+/// The canonical crossbeam-deque steal loop: local deque first, then a batch
+/// from the global injector, then a round-robin steal from sibling workers.
+fn find_task(
+    local: &LocalQueue<Job>,
+    injector: &Injector<Job>,
+    stealers: &Mutex<Vec<Stealer<Job>>>,
+) -> Option<Job> {
+    // Rotates the first victim on every sibling steal so no stealer is always tried first.
+    static NEXT_VICTIM: AtomicUsize = AtomicUsize::new(0);
+    local.pop().or_else(|| {
+        std::iter::repeat_with(|| {
+            injector.steal_batch_and_pop(local).or_else(|| {
+                let victims = stealers.lock().unwrap();
+                let start = NEXT_VICTIM.fetch_add(1, Ordering::Relaxed) % victims.len().max(1);
+                let stolen = victims
+                    .iter()
+                    .cycle()
+                    .skip(start)
+                    .take(victims.len())
+                    .map(|s| s.steal())
+                    .collect();
+                stolen
+            })
+        })
+        .find(|s| !s.is_retry())
+        .and_then(|s| s.success())
+    })
+}
 
 /// Coordination engine for managing distributed tasks
 pub struct CoordinationEngine {
-    task_scheduler: Arc<dyn TaskScheduler>,
-    resource_manager: Arc<dyn ResourceManager>,
-    worker_pool: WorkerPool,
-    consensus_engine: ConsensusEngine,
+    scheduler: WorkStealingScheduler,
 }
 
 impl CoordinationEngine {
-    /// Create a new coordination engine
+    /// Creates a coordination engine with one worker per available core and a
+    /// default echo handler.
     pub fn new() -> Self {
-        // TODO: Implement initialization
-        unimplemented!("CoordinationEngine::new")
+        Self::with_handler(Arc::new(default_handler), default_worker_count())
+    }
+
+    /// Creates a coordination engine backed by a work-stealing scheduler with
+    /// the given task handler and worker count.
+    pub fn with_handler(handler: TaskHandler, workers: usize) -> Self {
+        Self {
+            scheduler: WorkStealingScheduler::new(handler, workers.max(1)),
+        }
     }
-    
-    /// Coordinate a distributed task
+
+    /// Coordinates a distributed task by pushing it onto the scheduler's
+    /// injector and awaiting the worker's result.
     pub async fn coordinate_distributed_task(&self, task: Task) -> Result<TaskResult> {
-        // TODO: Implement task coordination
-        unimplemented!("coordinate_distributed_task")
+        let rx = self.scheduler.submit(task);
+        rx.recv()
+            .map_err(|_| crate::shared::error::ProtocolError::Task("worker dropped task".to_string()))
     }
-    
-    /// Manage worker lifecycle
+
+    /// Scales the worker pool toward the current backlog by adding at most one
+    /// worker per call, and only when there is pending work, no idle worker to
+    /// absorb it, and the pool cap has not been reached. Spawning one at a time
+    /// lets freshly started threads park before the next call decides whether
+    /// more are needed, avoiding a thread-spawn storm. Idle workers self-park,
+    /// so no explicit parking is needed here.
     pub async fn manage_worker_lifecycle(&self) -> Result<()> {
-        // TODO: Implement worker lifecycle management
-        unimplemented!("manage_worker_lifecycle")
+        if self.scheduler.backlog() > 0 && self.scheduler.parked_workers() == 0 {
+            self.scheduler.spawn_worker();
+        }
+        Ok(())
+    }
+}
+
+impl Default for CoordinationEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Default number of workers: one per core, at least one.
+fn default_worker_count() -> usize {
+    thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+/// Default handler that marks a task as successfully coordinated without
+/// transforming its payload.
+fn default_handler(task: Task) -> TaskResult {
+    TaskResult {
+        task_id: task.id,
+        success: true,
+        data: Some(task.payload),
+        error: None,
+        duration_ms: 0,
+        metrics: TaskMetrics {
+            cpu_usage: 0.0,
+            memory_bytes: 0,
+            network_sent: 0,
+            network_received: 0,
+        },
     }
 }
 
-// Placeholder traits
-trait TaskScheduler: Send + Sync {}
-trait ResourceManager: Send + Sync {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::types::{Priority, TaskStatus};
+    use chrono::Utc;
+    use std::collections::HashMap;
 
-// Placeholder structs
-struct WorkerPool;
-struct ConsensusEngine;
\ No newline at end of file
+    fn sample_task() -> Task {
+        Task {
+            id: uuid::Uuid::new_v4(),
+            task_type: "test".to_string(),
+            payload: serde_json::json!({"n": 1}),
+            priority: Priority::Normal,
+            status: TaskStatus::Pending,
+            assigned_worker: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_scheduler_runs_all_submitted_tasks() {
+        let scheduler = WorkStealingScheduler::new(Arc::new(default_handler), 4);
+        let receivers: Vec<_> = (0..32).map(|_| scheduler.submit(sample_task())).collect();
+        for rx in receivers {
+            let result = rx.recv().unwrap();
+            assert!(result.success);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_coordinate_distributed_task_returns_result() {
+        let engine = CoordinationEngine::with_handler(Arc::new(default_handler), 2);
+        let task = sample_task();
+        let id = task.id;
+        let result = engine.coordinate_distributed_task(task).await.unwrap();
+        assert_eq!(result.task_id, id);
+        assert!(result.success);
+    }
+
+    #[test]
+    fn test_worker_pool_is_capped() {
+        // A handler that blocks keeps every worker busy so no worker parks.
+        let blocker = Arc::new(|task: Task| {
+            thread::sleep(Duration::from_millis(50));
+            default_handler(task)
+        });
+        let scheduler = WorkStealingScheduler::new(blocker, 1);
+        for _ in 0..64 {
+            scheduler.submit(sample_task());
+        }
+        // Spawning far more times than the cap must never exceed max_workers.
+        for _ in 0..1000 {
+            scheduler.spawn_worker();
+        }
+        assert!(scheduler.worker_count() <= scheduler.max_workers());
+    }
+}
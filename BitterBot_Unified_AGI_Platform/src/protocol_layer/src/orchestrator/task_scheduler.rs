@@ -1,5 +1,9 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::shared::{error::ProtocolError, Result};
 
 /// Represents a task to be scheduled
 #[derive(Debug, Clone)]
@@ -9,10 +13,44 @@ pub struct Task {
     pub payload: Vec<u8>,
 }
 
+/// Parameters governing per-submitter credit accounting.
+///
+/// Modeled on the request-credit scheme used by light-client P2P protocols:
+/// every scheduled task costs `base_cost` plus `cost_per_byte` per payload
+/// byte, and each submitter's balance recharges by `recharge_rate` credits
+/// per second up to `max_credits`.
+#[derive(Debug, Clone)]
+pub struct FlowParams {
+    pub base_cost: u64,
+    pub cost_per_byte: u64,
+    pub recharge_rate: u64,
+    pub max_credits: u64,
+}
+
+impl Default for FlowParams {
+    fn default() -> Self {
+        Self {
+            base_cost: 1_000,
+            cost_per_byte: 1,
+            recharge_rate: 1_000,
+            max_credits: 100_000,
+        }
+    }
+}
+
+/// Credit balance tracked for a single submitter.
+#[derive(Debug, Clone)]
+pub struct Credits {
+    pub current: u64,
+    pub last_recharge: Instant,
+}
+
 /// TaskScheduler manages task distribution and scheduling
 pub struct TaskScheduler {
     task_queue: Arc<Mutex<VecDeque<Task>>>,
     max_queue_size: usize,
+    flow_params: FlowParams,
+    credits: Arc<Mutex<HashMap<String, Credits>>>,
 }
 
 impl TaskScheduler {
@@ -21,19 +59,115 @@ impl TaskScheduler {
         Self {
             task_queue: Arc::new(Mutex::new(VecDeque::new())),
             max_queue_size,
+            flow_params: FlowParams::default(),
+            credits: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    /// Overrides the flow-control parameters used for credit accounting.
+    pub fn with_flow_params(mut self, flow_params: FlowParams) -> Self {
+        self.flow_params = flow_params;
+        self
+    }
+
     /// Schedules a task for execution
-    pub fn schedule_task(&self, task: Task) -> Result<(), String> {
+    pub fn schedule_task(&self, task: Task) -> Result<()> {
         let mut queue = self.task_queue.lock().unwrap();
         if queue.len() >= self.max_queue_size {
-            return Err("Task queue is full".to_string());
+            return Err(ProtocolError::ResourceExhausted("Task queue is full".to_string()));
         }
         queue.push_back(task);
         Ok(())
     }
 
+    /// Schedules a task on behalf of `submitter_id`, charging its credit balance.
+    ///
+    /// The submitter's credits are first recharged for the elapsed time, then
+    /// the task cost (`base_cost + payload.len() * cost_per_byte`) is debited.
+    /// Submitters without enough credit are rejected before the task ever
+    /// touches the queue, so an abusive peer is throttled by cost rather than
+    /// raw task count.
+    pub fn schedule_task_for(&self, submitter_id: &str, task: Task) -> Result<()> {
+        let cost = self.task_cost(&task);
+
+        {
+            let mut credits = self.credits.lock().unwrap();
+            let entry = credits
+                .entry(submitter_id.to_string())
+                .or_insert_with(|| Credits {
+                    current: self.flow_params.max_credits,
+                    last_recharge: Instant::now(),
+                });
+            self.recharge(entry);
+            if entry.current < cost {
+                return Err(ProtocolError::ResourceExhausted(format!(
+                    "Submitter {} has {} credits, task costs {}",
+                    submitter_id, entry.current, cost
+                )));
+            }
+            entry.current -= cost;
+        }
+
+        // Refund the debited credits if the task cannot actually be enqueued,
+        // so a full queue never silently charges the submitter for dropped work.
+        match self.schedule_task(task) {
+            Ok(()) => Ok(()),
+            Err(err) => {
+                self.refund(submitter_id, cost);
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns `cost` credits to `submitter_id`, capped at `max_credits`.
+    fn refund(&self, submitter_id: &str, cost: u64) {
+        let mut credits = self.credits.lock().unwrap();
+        if let Some(entry) = credits.get_mut(submitter_id) {
+            entry.current = (entry.current + cost).min(self.flow_params.max_credits);
+        }
+    }
+
+    /// Returns the credits currently available to `submitter_id`.
+    ///
+    /// Recharge is applied lazily at read time so an idle submitter's balance
+    /// reflects the credits it has accrued since its last request.
+    pub fn available_credits(&self, submitter_id: &str) -> u64 {
+        let mut credits = self.credits.lock().unwrap();
+        let entry = credits
+            .entry(submitter_id.to_string())
+            .or_insert_with(|| Credits {
+                current: self.flow_params.max_credits,
+                last_recharge: Instant::now(),
+            });
+        self.recharge(entry);
+        entry.current
+    }
+
+    /// Attempts to schedule a task, applying backpressure until the deadline.
+    ///
+    /// Rather than failing immediately when the queue is full, this polls for
+    /// space and only returns `ResourceExhausted` once `deadline` has elapsed,
+    /// letting callers ride out transient bursts instead of dropping work.
+    pub fn try_schedule_with_deadline(&self, task: Task, deadline: Duration) -> Result<()> {
+        let start = Instant::now();
+        let poll_interval = Duration::from_millis(10);
+        loop {
+            {
+                let mut queue = self.task_queue.lock().unwrap();
+                if queue.len() < self.max_queue_size {
+                    queue.push_back(task);
+                    return Ok(());
+                }
+            }
+            if start.elapsed() >= deadline {
+                return Err(ProtocolError::ResourceExhausted(
+                    "Timed out waiting for queue space".to_string(),
+                ));
+            }
+            thread::sleep(poll_interval);
+        }
+    }
+
     /// Retrieves the next task to execute
     pub fn get_next_task(&self) -> Option<Task> {
         let mut queue = self.task_queue.lock().unwrap();
@@ -45,6 +179,26 @@ impl TaskScheduler {
         let queue = self.task_queue.lock().unwrap();
         queue.len()
     }
+
+    /// Computes the credit cost of a task from its payload size.
+    fn task_cost(&self, task: &Task) -> u64 {
+        self.flow_params.base_cost + task.payload.len() as u64 * self.flow_params.cost_per_byte
+    }
+
+    /// Recharges a credit balance in place based on elapsed wall-clock time.
+    fn recharge(&self, credits: &mut Credits) {
+        let now = Instant::now();
+        let elapsed_secs = now.duration_since(credits.last_recharge).as_secs();
+        if elapsed_secs == 0 {
+            return;
+        }
+        let recharged = elapsed_secs.saturating_mul(self.flow_params.recharge_rate);
+        credits.current = credits
+            .current
+            .saturating_add(recharged)
+            .min(self.flow_params.max_credits);
+        credits.last_recharge = now;
+    }
 }
 
 #[cfg(test)]
@@ -56,4 +210,49 @@ mod tests {
         let scheduler = TaskScheduler::new(100);
         assert_eq!(scheduler.pending_task_count(), 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_credit_exhaustion_rejects() {
+        let scheduler = TaskScheduler::new(100).with_flow_params(FlowParams {
+            base_cost: 10,
+            cost_per_byte: 0,
+            recharge_rate: 0,
+            max_credits: 15,
+        });
+        let task = Task {
+            id: "t1".to_string(),
+            priority: 1,
+            payload: vec![],
+        };
+        assert!(scheduler.schedule_task_for("peer", task.clone()).is_ok());
+        // Second task costs 10 but only 5 credits remain.
+        assert!(matches!(
+            scheduler.schedule_task_for("peer", task),
+            Err(ProtocolError::ResourceExhausted(_))
+        ));
+        assert_eq!(scheduler.available_credits("peer"), 5);
+    }
+
+    #[test]
+    fn test_full_queue_refunds_credits() {
+        let scheduler = TaskScheduler::new(1).with_flow_params(FlowParams {
+            base_cost: 10,
+            cost_per_byte: 0,
+            recharge_rate: 0,
+            max_credits: 100,
+        });
+        let task = Task {
+            id: "t1".to_string(),
+            priority: 1,
+            payload: vec![],
+        };
+        assert!(scheduler.schedule_task_for("peer", task.clone()).is_ok());
+        assert_eq!(scheduler.available_credits("peer"), 90);
+        // Queue is full: the task is rejected and its cost refunded.
+        assert!(matches!(
+            scheduler.schedule_task_for("peer", task),
+            Err(ProtocolError::ResourceExhausted(_))
+        ));
+        assert_eq!(scheduler.available_credits("peer"), 90);
+    }
+}
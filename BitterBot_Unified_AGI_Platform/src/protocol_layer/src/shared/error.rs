@@ -27,7 +27,7 @@ pub enum ProtocolError {
     
     /// Serialization errors
     #[error("Serialization error: {0}")]
-    Serialization(#[from] serde_json::Error),
+    Serialization(String),
     
     /// Configuration errors
     #[error("Configuration error: {0}")]
@@ -50,5 +50,11 @@ pub enum ProtocolError {
     Other(#[from] anyhow::Error),
 }
 
+impl From<serde_json::Error> for ProtocolError {
+    fn from(err: serde_json::Error) -> Self {
+        ProtocolError::Serialization(err.to_string())
+    }
+}
+
 /// Result type alias for protocol operations
 pub type Result<T> = std::result::Result<T, ProtocolError>;
\ No newline at end of file
@@ -1,5 +1,244 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
+use crate::shared::crypto::hash_data;
+
+/// A Merkle storage-inclusion proof carried in a [`Proof`]'s `data` field.
+///
+/// The submitter claims that `leaf` sits at position `leaf_index` in a Merkle
+/// tree whose root is `root`. `path` is the ordered list of sibling hashes
+/// from the leaf level up to the root; at level `i` the sibling is combined
+/// with the running hash on the left or right according to bit `i` of
+/// `leaf_index`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageProof {
+    /// Claimed Merkle root.
+    pub root: [u8; 32],
+    /// The leaf bytes whose inclusion is being proven.
+    pub leaf: Vec<u8>,
+    /// Position of the leaf in the tree.
+    pub leaf_index: u64,
+    /// Authentication path: sibling hashes from the leaf level upward.
+    pub path: Vec<[u8; 32]>,
+}
+
+impl StorageProof {
+    /// Serializes the proof into the length-prefixed little-endian layout used
+    /// as a [`Proof`]'s `data`.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(32 + 8 + 4 + self.leaf.len() + 4 + self.path.len() * 32);
+        buf.extend_from_slice(&self.root);
+        buf.extend_from_slice(&self.leaf_index.to_le_bytes());
+        buf.extend_from_slice(&(self.leaf.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&self.leaf);
+        buf.extend_from_slice(&(self.path.len() as u32).to_le_bytes());
+        for sibling in &self.path {
+            buf.extend_from_slice(sibling);
+        }
+        buf
+    }
+
+    /// Parses a proof from its encoded form, returning a descriptive error on a
+    /// truncated or malformed buffer.
+    pub fn decode(data: &[u8]) -> std::result::Result<Self, String> {
+        let mut cur = ProofCursor::new(data);
+        let mut root = [0u8; 32];
+        root.copy_from_slice(cur.take(32)?);
+        let leaf_index = cur.take_u64()?;
+        let leaf_len = cur.take_u32()? as usize;
+        let leaf = cur.take(leaf_len)?.to_vec();
+        let path_len = cur.take_u32()? as usize;
+        let mut path = Vec::with_capacity(path_len);
+        for _ in 0..path_len {
+            let mut sibling = [0u8; 32];
+            sibling.copy_from_slice(cur.take(32)?);
+            path.push(sibling);
+        }
+        Ok(StorageProof {
+            root,
+            leaf,
+            leaf_index,
+            path,
+        })
+    }
+
+    /// Verifies Merkle inclusion by folding the authentication path.
+    ///
+    /// Returns `Ok(())` when the recomputed root matches `root`, otherwise a
+    /// reason describing the mismatch. The path length must match the tree
+    /// depth implied by `leaf_index`: a leaf at index `i` requires a path long
+    /// enough that no bit of `i` lies above the path.
+    pub fn verify(&self) -> std::result::Result<(), String> {
+        if self.path.len() < 64 && self.leaf_index >> self.path.len() != 0 {
+            return Err(format!(
+                "leaf_index {} does not fit in a tree of depth {}",
+                self.leaf_index,
+                self.path.len()
+            ));
+        }
+
+        let mut current = hash_data(&self.leaf);
+        for (level, sibling) in self.path.iter().enumerate() {
+            let mut combined = Vec::with_capacity(64);
+            if (self.leaf_index >> level) & 1 == 0 {
+                combined.extend_from_slice(&current);
+                combined.extend_from_slice(sibling);
+            } else {
+                combined.extend_from_slice(sibling);
+                combined.extend_from_slice(&current);
+            }
+            current = hash_data(&combined);
+        }
+
+        if current.as_slice() == self.root {
+            Ok(())
+        } else {
+            Err("computed root does not match claimed root".to_string())
+        }
+    }
+}
+
+/// Minimal forward-only reader over an encoded [`StorageProof`].
+struct ProofCursor<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ProofCursor<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> std::result::Result<&'a [u8], String> {
+        let end = self.pos + n;
+        if end > self.buf.len() {
+            return Err("storage proof truncated".to_string());
+        }
+        let slice = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn take_u32(&mut self) -> std::result::Result<u32, String> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn take_u64(&mut self) -> std::result::Result<u64, String> {
+        let b = self.take(8)?;
+        Ok(u64::from_le_bytes([
+            b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7],
+        ]))
+    }
+}
+
+/// Seconds since the Unix epoch, used to stamp [`ValidationResult`]s.
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Splits a computation proof's `data` into its dispatch tag and the backend
+/// receipt that follows it.
+fn split_tag(data: &[u8]) -> std::result::Result<(String, &[u8]), String> {
+    let mut cur = ProofCursor::new(data);
+    let len = cur.take_u32()? as usize;
+    let tag_bytes = cur.take(len)?;
+    let tag = std::str::from_utf8(tag_bytes)
+        .map_err(|_| "invalid utf-8 in verifier tag".to_string())?
+        .to_string();
+    Ok((tag, &data[cur.pos..]))
+}
+
+/// A pluggable backend that verifies a [`ProofType::ComputationProof`] produced
+/// by a particular proving system. Implementations decode their own receipt
+/// format from the proof `data` (the dispatch tag included).
+pub trait ComputationVerifier: Send + Sync {
+    /// Verifies `proof`, returning a [`ValidationResult`] with a descriptive
+    /// reason on failure.
+    fn verify(&self, proof: &Proof) -> ValidationResult;
+}
+
+/// risc0-style zero-knowledge receipt verifier.
+///
+/// Receipt layout after the tag: a 32-byte program image id, a length-prefixed
+/// block of expected public outputs, and a 32-byte journal digest. The receipt
+/// is accepted when the digest commits to the image id and the public outputs.
+pub struct Risc0ReceiptVerifier;
+
+impl ComputationVerifier for Risc0ReceiptVerifier {
+    fn verify(&self, proof: &Proof) -> ValidationResult {
+        let reason = (|| -> std::result::Result<(), String> {
+            let (_, receipt) = split_tag(&proof.data)?;
+            let mut cur = ProofCursor::new(receipt);
+            let image_id = cur.take(32)?;
+            let outputs_len = cur.take_u32()? as usize;
+            let outputs = cur.take(outputs_len)?;
+            let journal = cur.take(32)?;
+            let mut committed = Vec::with_capacity(32 + outputs.len());
+            committed.extend_from_slice(image_id);
+            committed.extend_from_slice(outputs);
+            if hash_data(&committed).as_slice() == journal {
+                Ok(())
+            } else {
+                Err("receipt journal does not commit to image id and outputs".to_string())
+            }
+        })()
+        .err();
+        ValidationResult {
+            is_valid: reason.is_none(),
+            reason,
+            timestamp: now_secs(),
+        }
+    }
+}
+
+/// SGX enclave attestation/quote verifier.
+///
+/// Receipt layout after the tag: a 32-byte enclave measurement and a 32-byte
+/// quote signature. The quote is accepted when the measurement is on the
+/// allowlist and its signature matches the expected binding.
+pub struct SgxAttestationVerifier {
+    allowed_measurements: HashSet<[u8; 32]>,
+}
+
+impl SgxAttestationVerifier {
+    /// Creates a verifier trusting the given enclave measurements.
+    pub fn new(allowed_measurements: HashSet<[u8; 32]>) -> Self {
+        Self {
+            allowed_measurements,
+        }
+    }
+}
+
+impl ComputationVerifier for SgxAttestationVerifier {
+    fn verify(&self, proof: &Proof) -> ValidationResult {
+        let reason = (|| -> std::result::Result<(), String> {
+            let (_, receipt) = split_tag(&proof.data)?;
+            let mut cur = ProofCursor::new(receipt);
+            let mut measurement = [0u8; 32];
+            measurement.copy_from_slice(cur.take(32)?);
+            let signature = cur.take(32)?;
+            if !self.allowed_measurements.contains(&measurement) {
+                return Err("enclave measurement not on allowlist".to_string());
+            }
+            if hash_data(&measurement).as_slice() == signature {
+                Ok(())
+            } else {
+                Err("invalid enclave quote signature".to_string())
+            }
+        })()
+        .err();
+        ValidationResult {
+            is_valid: reason.is_none(),
+            reason,
+            timestamp: now_secs(),
+        }
+    }
+}
+
 /// Types of proofs that can be validated
 #[derive(Debug, Clone, PartialEq)]
 pub enum ProofType {
@@ -31,17 +270,36 @@ pub struct ValidationResult {
 pub struct ProofValidator {
     validation_count: Arc<Mutex<u64>>,
     difficulty_threshold: u64,
+    computation_verifiers: HashMap<String, Box<dyn ComputationVerifier>>,
 }
 
 impl ProofValidator {
-    /// Creates a new ProofValidator instance
+    /// Creates a new ProofValidator instance with the built-in computation
+    /// verifier backends registered.
     pub fn new(difficulty_threshold: u64) -> Self {
+        let mut computation_verifiers: HashMap<String, Box<dyn ComputationVerifier>> =
+            HashMap::new();
+        computation_verifiers.insert("risc0".to_string(), Box::new(Risc0ReceiptVerifier));
+        computation_verifiers.insert(
+            "sgx".to_string(),
+            Box::new(SgxAttestationVerifier::new(HashSet::new())),
+        );
         Self {
             validation_count: Arc::new(Mutex::new(0)),
             difficulty_threshold,
+            computation_verifiers,
         }
     }
 
+    /// Registers (or replaces) a computation verifier backend under `tag`.
+    pub fn register_computation_verifier(
+        &mut self,
+        tag: impl Into<String>,
+        verifier: Box<dyn ComputationVerifier>,
+    ) {
+        self.computation_verifiers.insert(tag.into(), verifier);
+    }
+
     /// Validates a proof
     pub fn validate_proof(&self, proof: &Proof) -> ValidationResult {
         let mut count = self.validation_count.lock().unwrap();
@@ -85,33 +343,41 @@ impl ProofValidator {
         }
     }
 
-    /// Validates a computation proof
+    /// Validates a computation proof by dispatching to the backend named by
+    /// the proof's embedded tag, failing closed when none matches.
     fn validate_computation_proof(&self, proof: &Proof) -> ValidationResult {
-        // Stub implementation
-        ValidationResult {
-            is_valid: proof.data.len() > 0,
-            reason: if proof.data.is_empty() {
-                Some("Empty computation proof".to_string())
-            } else {
-                None
+        let tag = match split_tag(&proof.data) {
+            Ok((tag, _)) => tag,
+            Err(reason) => {
+                return ValidationResult {
+                    is_valid: false,
+                    reason: Some(reason),
+                    timestamp: now_secs(),
+                };
+            }
+        };
+        match self.computation_verifiers.get(&tag) {
+            Some(verifier) => verifier.verify(proof),
+            None => ValidationResult {
+                is_valid: false,
+                reason: Some(format!("no computation verifier registered for tag '{}'", tag)),
+                timestamp: now_secs(),
             },
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
         }
     }
 
-    /// Validates a storage proof
+    /// Validates a storage proof via Merkle inclusion verification
     fn validate_storage_proof(&self, proof: &Proof) -> ValidationResult {
-        // Stub implementation
-        ValidationResult {
-            is_valid: proof.data.len() >= 64,
-            reason: if proof.data.len() < 64 {
-                Some("Invalid storage proof size".to_string())
-            } else {
-                None
+        let (is_valid, reason) = match StorageProof::decode(&proof.data) {
+            Ok(storage_proof) => match storage_proof.verify() {
+                Ok(()) => (true, None),
+                Err(reason) => (false, Some(reason)),
             },
+            Err(reason) => (false, Some(reason)),
+        };
+        ValidationResult {
+            is_valid,
+            reason,
             timestamp: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
@@ -134,4 +400,138 @@ mod tests {
         let validator = ProofValidator::new(1000);
         assert_eq!(validator.get_validation_count(), 0);
     }
+
+    fn node(left: &[u8], right: &[u8]) -> [u8; 32] {
+        let mut combined = Vec::with_capacity(64);
+        combined.extend_from_slice(left);
+        combined.extend_from_slice(right);
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&hash_data(&combined));
+        out
+    }
+
+    #[test]
+    fn test_valid_storage_proof_verifies() {
+        // A four-leaf tree; prove inclusion of leaf 0.
+        let leaves: Vec<Vec<u8>> = vec![vec![0], vec![1], vec![2], vec![3]];
+        let h: Vec<[u8; 32]> = leaves
+            .iter()
+            .map(|l| {
+                let mut a = [0u8; 32];
+                a.copy_from_slice(&hash_data(l));
+                a
+            })
+            .collect();
+        let n0 = node(&h[0], &h[1]);
+        let n1 = node(&h[2], &h[3]);
+        let root = node(&n0, &n1);
+
+        let proof = StorageProof {
+            root,
+            leaf: leaves[0].clone(),
+            leaf_index: 0,
+            path: vec![h[1], n1],
+        };
+        assert!(proof.verify().is_ok());
+
+        let validator = ProofValidator::new(1000);
+        let result = validator.validate_proof(&Proof {
+            id: "p1".to_string(),
+            proof_type: ProofType::StorageProof,
+            data: proof.encode(),
+            timestamp: 0,
+            submitter: "node-a".to_string(),
+        });
+        assert!(result.is_valid, "{:?}", result.reason);
+    }
+
+    #[test]
+    fn test_tampered_leaf_is_rejected() {
+        let proof = StorageProof {
+            root: [7u8; 32],
+            leaf: vec![9, 9, 9],
+            leaf_index: 0,
+            path: vec![[1u8; 32]],
+        };
+        assert!(proof.verify().is_err());
+    }
+
+    fn tagged(tag: &str, receipt: &[u8]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&(tag.len() as u32).to_le_bytes());
+        data.extend_from_slice(tag.as_bytes());
+        data.extend_from_slice(receipt);
+        data
+    }
+
+    fn computation_proof(data: Vec<u8>) -> Proof {
+        Proof {
+            id: "c1".to_string(),
+            proof_type: ProofType::ComputationProof,
+            data,
+            timestamp: 0,
+            submitter: "node-a".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_risc0_receipt_verifies() {
+        let image_id = [4u8; 32];
+        let outputs = [9u8, 8, 7];
+        let mut committed = Vec::new();
+        committed.extend_from_slice(&image_id);
+        committed.extend_from_slice(&outputs);
+        let journal = hash_data(&committed);
+
+        let mut receipt = Vec::new();
+        receipt.extend_from_slice(&image_id);
+        receipt.extend_from_slice(&(outputs.len() as u32).to_le_bytes());
+        receipt.extend_from_slice(&outputs);
+        receipt.extend_from_slice(&journal);
+
+        let validator = ProofValidator::new(1000);
+        let result = validator.validate_proof(&computation_proof(tagged("risc0", &receipt)));
+        assert!(result.is_valid, "{:?}", result.reason);
+    }
+
+    #[test]
+    fn test_sgx_attestation_checks_allowlist() {
+        let measurement = [5u8; 32];
+        let signature = hash_data(&measurement);
+        let mut receipt = Vec::new();
+        receipt.extend_from_slice(&measurement);
+        receipt.extend_from_slice(&signature);
+
+        let mut validator = ProofValidator::new(1000);
+        // The default sgx backend has an empty allowlist and rejects.
+        let rejected = validator.validate_proof(&computation_proof(tagged("sgx", &receipt)));
+        assert!(!rejected.is_valid);
+
+        let mut allowlist = HashSet::new();
+        allowlist.insert(measurement);
+        validator.register_computation_verifier(
+            "sgx",
+            Box::new(SgxAttestationVerifier::new(allowlist)),
+        );
+        let accepted = validator.validate_proof(&computation_proof(tagged("sgx", &receipt)));
+        assert!(accepted.is_valid, "{:?}", accepted.reason);
+    }
+
+    #[test]
+    fn test_unknown_computation_tag_fails_closed() {
+        let validator = ProofValidator::new(1000);
+        let result = validator.validate_proof(&computation_proof(tagged("snark", &[1, 2, 3])));
+        assert!(!result.is_valid);
+    }
+
+    #[test]
+    fn test_path_length_must_match_index() {
+        let proof = StorageProof {
+            root: [0u8; 32],
+            leaf: vec![1],
+            leaf_index: 5,
+            path: vec![[0u8; 32]],
+        };
+        assert!(proof.verify().is_err());
+    }
 }
\ No newline at end of file
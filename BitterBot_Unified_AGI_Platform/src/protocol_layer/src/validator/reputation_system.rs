@@ -26,6 +26,11 @@ pub struct ReputationSystem {
     base_score: f64,
     max_score: f64,
     min_score: f64,
+    /// Decay time constant in seconds: a validator's score relaxes toward
+    /// `base_score` by `exp(-elapsed_secs / tau)` between updates.
+    tau: f64,
+    /// EMA smoothing factor applied to each event's target value.
+    alpha: f64,
 }
 
 impl ReputationSystem {
@@ -36,9 +41,23 @@ impl ReputationSystem {
             base_score,
             max_score,
             min_score,
+            tau: 3600.0,
+            alpha: 0.3,
         }
     }
 
+    /// Overrides the decay time constant `tau` and EMA smoothing factor `alpha`.
+    pub fn with_dynamics(mut self, tau: f64, alpha: f64) -> Self {
+        self.tau = tau;
+        self.alpha = alpha;
+        self
+    }
+
+    /// The lower bound scores are clamped to.
+    pub fn min_score(&self) -> f64 {
+        self.min_score
+    }
+
     /// Registers a new validator with base reputation
     pub fn register_validator(&self, validator_id: String) -> Result<(), String> {
         let mut scores = self.scores.write().unwrap();
@@ -61,37 +80,88 @@ impl ReputationSystem {
         Ok(())
     }
 
-    /// Updates a validator's reputation based on an event
+    /// Updates a validator's reputation based on an event.
+    ///
+    /// The stored score is first decayed toward `base_score` for the time
+    /// elapsed since the last update, then nudged toward the event's target
+    /// value by an exponential moving average, and finally clamped to
+    /// `[min_score, max_score]`. This lets recent behaviour dominate without
+    /// either permanent blacklisting or stale high scores.
     pub fn update_reputation(&self, validator_id: &str, event: ReputationEvent) -> Result<(), String> {
+        let now = Self::now_secs();
         let mut scores = self.scores.write().unwrap();
         match scores.get_mut(validator_id) {
             Some(score) => {
                 match event {
-                    ReputationEvent::SuccessfulValidation => {
-                        score.successful_validations += 1;
-                        score.score = (score.score + 1.0).min(self.max_score);
-                    }
-                    ReputationEvent::FailedValidation => {
-                        score.failed_validations += 1;
-                        score.score = (score.score - 2.0).max(self.min_score);
-                    }
-                    ReputationEvent::MissedValidation => {
-                        score.score = (score.score - 1.0).max(self.min_score);
-                    }
-                    ReputationEvent::MaliciousBehavior => {
-                        score.score = (score.score - 10.0).max(self.min_score);
-                    }
+                    ReputationEvent::SuccessfulValidation => score.successful_validations += 1,
+                    ReputationEvent::FailedValidation => score.failed_validations += 1,
+                    _ => {}
                 }
-                score.last_updated = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs();
+
+                let decayed = self.decay(score.score, score.last_updated, now);
+                let target = self.event_value(&event);
+                score.score = (self.alpha * target + (1.0 - self.alpha) * decayed)
+                    .clamp(self.min_score, self.max_score);
+                score.last_updated = now;
                 Ok(())
             }
             None => Err("Validator not found".to_string()),
         }
     }
 
+    /// Returns a validator's score with time decay applied lazily at read time,
+    /// so idle validators drift back toward neutral without an update event.
+    pub fn effective_score(&self, validator_id: &str) -> Option<f64> {
+        let now = Self::now_secs();
+        let scores = self.scores.read().unwrap();
+        scores
+            .get(validator_id)
+            .map(|s| self.decay(s.score, s.last_updated, now))
+    }
+
+    /// Success-rate-weighted confidence in a validator, in `[0.0, 1.0]`.
+    ///
+    /// Combines the raw success ratio with the volume of observations so a
+    /// validator with a handful of successes is trusted less than one with a
+    /// long clean record.
+    pub fn confidence(&self, validator_id: &str) -> Option<f64> {
+        let scores = self.scores.read().unwrap();
+        scores.get(validator_id).map(|s| {
+            let total = s.successful_validations + s.failed_validations;
+            if total == 0 {
+                return 0.0;
+            }
+            let success_rate = s.successful_validations as f64 / total as f64;
+            let volume_weight = total as f64 / (total as f64 + 10.0);
+            success_rate * volume_weight
+        })
+    }
+
+    /// Relaxes `score` toward `base_score` over the elapsed interval.
+    fn decay(&self, score: f64, last_updated: u64, now: u64) -> f64 {
+        let elapsed = now.saturating_sub(last_updated) as f64;
+        let factor = (-elapsed / self.tau).exp();
+        self.base_score + (score - self.base_score) * factor
+    }
+
+    /// Maps an event to the score value the EMA should move toward.
+    fn event_value(&self, event: &ReputationEvent) -> f64 {
+        match event {
+            ReputationEvent::SuccessfulValidation => self.max_score,
+            ReputationEvent::FailedValidation => self.base_score - (self.base_score - self.min_score) * 0.5,
+            ReputationEvent::MissedValidation => self.base_score,
+            ReputationEvent::MaliciousBehavior => self.min_score,
+        }
+    }
+
+    /// Current UNIX time in seconds.
+    fn now_secs() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
     /// Gets a validator's reputation score
     pub fn get_reputation(&self, validator_id: &str) -> Option<ReputationScore> {
         let scores = self.scores.read().unwrap();
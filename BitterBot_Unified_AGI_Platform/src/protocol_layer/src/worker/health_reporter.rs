@@ -36,12 +36,50 @@ pub trait HealthCheckable {
     fn get_name(&self) -> String;
 }
 
-/// HealthReporter manages health checks for worker components
+/// Membership state for a tracked member.
+///
+/// The `Alive → Suspect → Unhealthy` progression and incarnation-based
+/// refutation are borrowed from SWIM, but the full protocol's indirect probes
+/// and ping/ack piggybacking are not implemented here — see [`HealthReporter`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MemberState {
+    /// The member has responded to a recent probe.
+    Alive,
+    /// A probe failed; the member is suspected as of the given instant.
+    Suspect(Instant),
+    /// The member is confirmed failed after the suspicion timeout.
+    Unhealthy,
+}
+
+/// A member of the failure-detection group.
+#[derive(Debug, Clone)]
+pub struct Member {
+    /// Member identifier (matches the registered component name).
+    pub name: String,
+    /// Incarnation number, bumped when a member refutes a suspicion.
+    pub incarnation: u64,
+    /// Current membership state.
+    pub state: MemberState,
+}
+
+/// HealthReporter manages health checks for worker components.
+///
+/// It runs a lightweight, SWIM-inspired failure detector over locally
+/// registered components: [`tick`](Self::tick) probes one random member per
+/// period and drives the `Alive → Suspect → Unhealthy` state machine, with
+/// incarnation-based [`refute`](Self::refute). It is deliberately *not* a full
+/// SWIM implementation — the indirect-probe phase (asking peers to probe a
+/// target over their own path) and ping/ack piggybacking for epidemic
+/// dissemination both require an inter-node transport with a responder, which
+/// this local-component model does not provide.
 pub struct HealthReporter {
     components: Arc<RwLock<HashMap<String, Box<dyn HealthCheckable + Send + Sync>>>>,
     check_results: Arc<RwLock<HashMap<String, HealthCheckResult>>>,
     start_time: Instant,
     check_interval: Duration,
+    members: Arc<RwLock<HashMap<String, Member>>>,
+    ping_timeout: Duration,
+    suspicion_timeout: Duration,
 }
 
 impl HealthReporter {
@@ -52,13 +90,136 @@ impl HealthReporter {
             check_results: Arc::new(RwLock::new(HashMap::new())),
             start_time: Instant::now(),
             check_interval,
+            members: Arc::new(RwLock::new(HashMap::new())),
+            ping_timeout: Duration::from_secs(2),
+            suspicion_timeout: Duration::from_secs(5),
         }
     }
 
+    /// The direct-ping timeout used by [`tick`](Self::tick).
+    pub fn ping_timeout(&self) -> Duration {
+        self.ping_timeout
+    }
+
     /// Registers a component for health checking
     pub fn register_component(&self, name: String, component: Box<dyn HealthCheckable + Send + Sync>) {
         let mut components = self.components.write().unwrap();
-        components.insert(name, component);
+        components.insert(name.clone(), component);
+        drop(components);
+        self.members.write().unwrap().entry(name.clone()).or_insert(Member {
+            name,
+            incarnation: 0,
+            state: MemberState::Alive,
+        });
+    }
+
+    /// Performs one detection period: probe a single random member, suspecting
+    /// it when the probe fails, and promote suspects that have outlived the
+    /// suspicion timeout to `Unhealthy`.
+    ///
+    /// This does bounded per-tick work regardless of group size, unlike the
+    /// O(n) [`run_health_checks`](Self::run_health_checks) sweep.
+    ///
+    /// Classic SWIM falls back to `k` *indirect* probes — asking other members
+    /// to reach the target over their own network path — before suspecting.
+    /// Members here are probed through locally registered [`HealthCheckable`]
+    /// components, so a helper has no distinct path to the target and an
+    /// indirect probe would just re-run the identical deterministic check; the
+    /// phase is therefore omitted rather than faked (see [`HealthReporter`]).
+    pub fn tick(&self) {
+        self.promote_expired_suspects();
+
+        let target = self.random_member(None);
+        let target = match target {
+            Some(name) => name,
+            None => return,
+        };
+
+        if self.probe(&target) {
+            self.set_alive(&target);
+        } else {
+            self.mark_suspect(&target);
+        }
+    }
+
+    /// Marks a member as suspect (from `Alive`), starting its suspicion timer.
+    pub fn mark_suspect(&self, name: &str) {
+        let mut members = self.members.write().unwrap();
+        if let Some(member) = members.get_mut(name) {
+            if matches!(member.state, MemberState::Alive) {
+                member.state = MemberState::Suspect(Instant::now());
+            }
+        }
+    }
+
+    /// Refutes a suspicion with a higher incarnation number, restoring `Alive`.
+    pub fn refute(&self, name: &str, incarnation: u64) -> bool {
+        let mut members = self.members.write().unwrap();
+        match members.get_mut(name) {
+            Some(member) if incarnation > member.incarnation => {
+                member.incarnation = incarnation;
+                member.state = MemberState::Alive;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns a snapshot of the current membership list.
+    pub fn membership_report(&self) -> Vec<Member> {
+        self.members.read().unwrap().values().cloned().collect()
+    }
+
+    /// Promotes suspects whose suspicion timer has expired to `Unhealthy`.
+    fn promote_expired_suspects(&self) {
+        let now = Instant::now();
+        let mut members = self.members.write().unwrap();
+        for member in members.values_mut() {
+            if let MemberState::Suspect(since) = member.state {
+                if now.duration_since(since) >= self.suspicion_timeout {
+                    member.state = MemberState::Unhealthy;
+                }
+            }
+        }
+    }
+
+    /// Marks a member `Alive`, clearing any suspicion.
+    fn set_alive(&self, name: &str) {
+        let mut members = self.members.write().unwrap();
+        if let Some(member) = members.get_mut(name) {
+            member.state = MemberState::Alive;
+        }
+    }
+
+    /// Probes a member via its registered component, honouring the ping timeout.
+    fn probe(&self, name: &str) -> bool {
+        let components = self.components.read().unwrap();
+        match components.get(name) {
+            Some(component) => {
+                let result = component.check_health();
+                result.status != HealthStatus::Unhealthy
+                    && result.response_time_ms <= self.ping_timeout.as_millis() as u64
+            }
+            None => false,
+        }
+    }
+
+    /// Picks a single random member, optionally excluding one name.
+    fn random_member(&self, exclude: Option<&str>) -> Option<String> {
+        self.random_members(1, exclude).into_iter().next()
+    }
+
+    /// Picks up to `n` random member names, optionally excluding one.
+    fn random_members(&self, n: usize, exclude: Option<&str>) -> Vec<String> {
+        use rand::seq::SliceRandom;
+        let members = self.members.read().unwrap();
+        let candidates: Vec<String> = members
+            .keys()
+            .filter(|name| Some(name.as_str()) != exclude)
+            .cloned()
+            .collect();
+        let mut rng = rand::thread_rng();
+        candidates.choose_multiple(&mut rng, n).cloned().collect()
     }
 
     /// Performs health checks on all registered components
@@ -1,4 +1,8 @@
-use std::sync::{Arc, RwLock};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 
 /// System resource metrics
@@ -23,7 +27,7 @@ pub struct ResourceAlert {
     pub timestamp: Instant,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AlertType {
     HighCpuUsage,
     HighMemoryUsage,
@@ -31,12 +35,94 @@ pub enum AlertType {
     NetworkCongestion,
 }
 
+/// Upper bound on retained alerts; the oldest are dropped past this, keeping
+/// [`ResourceMonitor::get_alerts`] bounded under a sustained threshold breach.
+const MAX_RETAINED_ALERTS: usize = 256;
+
+/// A raw host reading produced by a [`MetricsSource`].
+#[derive(Debug, Clone)]
+pub struct ResourceSample {
+    pub cpu_usage_percent: f64,
+    pub memory_usage_bytes: u64,
+    pub memory_total_bytes: u64,
+    pub disk_usage_bytes: u64,
+    pub disk_total_bytes: u64,
+    pub network_rx_bytes: u64,
+    pub network_tx_bytes: u64,
+}
+
+/// Source of host resource readings. Implemented by a sysinfo-backed source in
+/// production and by fixtures in tests, keeping the sampler independent of the
+/// underlying platform API.
+pub trait MetricsSource: Send + Sync {
+    /// Reads the current host resource usage.
+    fn sample(&self) -> ResourceSample;
+}
+
+/// sysinfo-style source that reads true figures from the host.
+///
+/// The `sysinfo::System` is retained between samples so CPU usage can be
+/// computed from the delta against the previous refresh.
+pub struct SysinfoSource {
+    system: Mutex<sysinfo::System>,
+}
+
+impl SysinfoSource {
+    /// Creates a source over a freshly initialized system snapshot.
+    pub fn new() -> Self {
+        Self {
+            system: Mutex::new(sysinfo::System::new_all()),
+        }
+    }
+}
+
+impl Default for SysinfoSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetricsSource for SysinfoSource {
+    fn sample(&self) -> ResourceSample {
+        let mut system = self.system.lock().unwrap();
+        system.refresh_cpu();
+        system.refresh_memory();
+
+        let cpu_usage_percent = system.global_cpu_info().cpu_usage() as f64;
+        let memory_usage_bytes = system.used_memory();
+        let memory_total_bytes = system.total_memory();
+
+        let disks = sysinfo::Disks::new_with_refreshed_list();
+        let disk_total_bytes: u64 = disks.iter().map(|d| d.total_space()).sum();
+        let disk_used: u64 = disks
+            .iter()
+            .map(|d| d.total_space().saturating_sub(d.available_space()))
+            .sum();
+
+        let networks = sysinfo::Networks::new_with_refreshed_list();
+        let network_rx_bytes: u64 = networks.iter().map(|(_, n)| n.total_received()).sum();
+        let network_tx_bytes: u64 = networks.iter().map(|(_, n)| n.total_transmitted()).sum();
+
+        ResourceSample {
+            cpu_usage_percent,
+            memory_usage_bytes,
+            memory_total_bytes,
+            disk_usage_bytes: disk_used,
+            disk_total_bytes,
+            network_rx_bytes,
+            network_tx_bytes,
+        }
+    }
+}
+
 /// Configuration for resource monitoring
 #[derive(Debug, Clone)]
 pub struct MonitorConfig {
     pub cpu_threshold: f64,
     pub memory_threshold: f64,
     pub disk_threshold: f64,
+    /// Network throughput above which a congestion alert fires, in bytes/sec.
+    pub network_threshold_bytes_per_sec: f64,
     pub sample_interval_ms: u64,
 }
 
@@ -45,12 +131,21 @@ pub struct ResourceMonitor {
     config: MonitorConfig,
     current_metrics: Arc<RwLock<ResourceMetrics>>,
     alerts: Arc<RwLock<Vec<ResourceAlert>>>,
-    monitoring_active: Arc<RwLock<bool>>,
+    monitoring_active: Arc<AtomicBool>,
+    source: Arc<dyn MetricsSource>,
+    metric_subscribers: Arc<Mutex<Vec<Sender<ResourceMetrics>>>>,
+    alert_subscribers: Arc<Mutex<Vec<Sender<ResourceAlert>>>>,
+    sampler: Mutex<Option<JoinHandle<()>>>,
 }
 
 impl ResourceMonitor {
-    /// Creates a new ResourceMonitor instance
+    /// Creates a new ResourceMonitor instance reading from the host.
     pub fn new(config: MonitorConfig) -> Self {
+        Self::with_source(config, Arc::new(SysinfoSource::new()))
+    }
+
+    /// Creates a ResourceMonitor reading from a custom [`MetricsSource`].
+    pub fn with_source(config: MonitorConfig, source: Arc<dyn MetricsSource>) -> Self {
         let initial_metrics = ResourceMetrics {
             cpu_usage_percent: 0.0,
             memory_usage_bytes: 0,
@@ -66,65 +161,114 @@ impl ResourceMonitor {
             config,
             current_metrics: Arc::new(RwLock::new(initial_metrics)),
             alerts: Arc::new(RwLock::new(Vec::new())),
-            monitoring_active: Arc::new(RwLock::new(false)),
+            monitoring_active: Arc::new(AtomicBool::new(false)),
+            source,
+            metric_subscribers: Arc::new(Mutex::new(Vec::new())),
+            alert_subscribers: Arc::new(Mutex::new(Vec::new())),
+            sampler: Mutex::new(None),
         }
     }
 
-    /// Starts resource monitoring
+    /// Starts resource monitoring, spawning a background sampler that refreshes
+    /// metrics every `sample_interval_ms` until [`stop_monitoring`] is called.
+    ///
+    /// [`stop_monitoring`]: Self::stop_monitoring
     pub fn start_monitoring(&self) {
-        let mut active = self.monitoring_active.write().unwrap();
-        *active = true;
-        
-        // In a real implementation, this would spawn a monitoring thread
-        self.update_metrics();
+        if self.monitoring_active.swap(true, Ordering::AcqRel) {
+            return; // already running
+        }
+
+        let interval = Duration::from_millis(self.config.sample_interval_ms.max(1));
+        let config = self.config.clone();
+        let source = Arc::clone(&self.source);
+        let current_metrics = Arc::clone(&self.current_metrics);
+        let alerts = Arc::clone(&self.alerts);
+        let active = Arc::clone(&self.monitoring_active);
+        let metric_subscribers = Arc::clone(&self.metric_subscribers);
+        let alert_subscribers = Arc::clone(&self.alert_subscribers);
+
+        let handle = thread::spawn(move || {
+            let mut previous: Option<(u64, u64)> = None;
+            // Alert types currently breaching their threshold, so alerts fire
+            // only on the transition into breach rather than every tick.
+            let mut active_alerts: HashSet<AlertType> = HashSet::new();
+            while active.load(Ordering::Acquire) {
+                let sample = source.sample();
+                let metrics = ResourceMetrics {
+                    cpu_usage_percent: sample.cpu_usage_percent,
+                    memory_usage_bytes: sample.memory_usage_bytes,
+                    memory_total_bytes: sample.memory_total_bytes,
+                    disk_usage_bytes: sample.disk_usage_bytes,
+                    disk_total_bytes: sample.disk_total_bytes,
+                    network_rx_bytes: sample.network_rx_bytes,
+                    network_tx_bytes: sample.network_tx_bytes,
+                    timestamp: Instant::now(),
+                };
+
+                // Network rate since the previous sample, in bytes/sec.
+                let net_rate = match previous {
+                    Some((prev_rx, prev_tx)) => {
+                        let delta = metrics
+                            .network_rx_bytes
+                            .saturating_sub(prev_rx)
+                            .saturating_add(metrics.network_tx_bytes.saturating_sub(prev_tx));
+                        delta as f64 / interval.as_secs_f64()
+                    }
+                    None => 0.0,
+                };
+                previous = Some((metrics.network_rx_bytes, metrics.network_tx_bytes));
+
+                *current_metrics.write().unwrap() = metrics.clone();
+                broadcast(&metric_subscribers, &metrics);
+
+                let fired = evaluate_alerts(&metrics, &config, net_rate);
+                let firing: HashSet<AlertType> =
+                    fired.iter().map(|a| a.alert_type.clone()).collect();
+                {
+                    let mut store = alerts.write().unwrap();
+                    for alert in &fired {
+                        // Edge-triggered: skip alerts already active last tick.
+                        if active_alerts.contains(&alert.alert_type) {
+                            continue;
+                        }
+                        if store.len() >= MAX_RETAINED_ALERTS {
+                            store.remove(0);
+                        }
+                        store.push(alert.clone());
+                        broadcast(&alert_subscribers, alert);
+                    }
+                }
+                active_alerts = firing;
+
+                thread::sleep(interval);
+            }
+        });
+
+        *self.sampler.lock().unwrap() = Some(handle);
     }
 
-    /// Stops resource monitoring
+    /// Stops resource monitoring, joining the sampling thread.
     pub fn stop_monitoring(&self) {
-        let mut active = self.monitoring_active.write().unwrap();
-        *active = false;
-    }
-
-    /// Updates resource metrics (stub implementation)
-    fn update_metrics(&self) {
-        let mut metrics = self.current_metrics.write().unwrap();
-        
-        // Simulate metric updates
-        metrics.cpu_usage_percent = 25.0;
-        metrics.memory_usage_bytes = 2 * 1024 * 1024 * 1024; // 2GB
-        metrics.disk_usage_bytes = 30 * 1024 * 1024 * 1024; // 30GB
-        metrics.network_rx_bytes += 1024;
-        metrics.network_tx_bytes += 512;
-        metrics.timestamp = Instant::now();
-        
-        // Check for alerts
-        self.check_alerts(&metrics);
-    }
-
-    /// Checks current metrics against thresholds
-    fn check_alerts(&self, metrics: &ResourceMetrics) {
-        let mut alerts = self.alerts.write().unwrap();
-        
-        if metrics.cpu_usage_percent > self.config.cpu_threshold {
-            alerts.push(ResourceAlert {
-                alert_type: AlertType::HighCpuUsage,
-                metric_value: metrics.cpu_usage_percent,
-                threshold: self.config.cpu_threshold,
-                timestamp: Instant::now(),
-            });
-        }
-        
-        let memory_usage_percent = (metrics.memory_usage_bytes as f64 / metrics.memory_total_bytes as f64) * 100.0;
-        if memory_usage_percent > self.config.memory_threshold {
-            alerts.push(ResourceAlert {
-                alert_type: AlertType::HighMemoryUsage,
-                metric_value: memory_usage_percent,
-                threshold: self.config.memory_threshold,
-                timestamp: Instant::now(),
-            });
+        self.monitoring_active.store(false, Ordering::Release);
+        if let Some(handle) = self.sampler.lock().unwrap().take() {
+            let _ = handle.join();
         }
     }
 
+    /// Subscribes to the stream of sampled metrics.
+    pub fn subscribe(&self) -> Receiver<ResourceMetrics> {
+        let (tx, rx) = mpsc::channel();
+        self.metric_subscribers.lock().unwrap().push(tx);
+        rx
+    }
+
+    /// Subscribes to the stream of threshold alerts.
+    pub fn subscribe_alerts(&self) -> Receiver<ResourceAlert> {
+        let (tx, rx) = mpsc::channel();
+        self.alert_subscribers.lock().unwrap().push(tx);
+        rx
+    }
+
     /// Gets current resource metrics
     pub fn get_current_metrics(&self) -> ResourceMetrics {
         self.current_metrics.read().unwrap().clone()
@@ -143,8 +287,74 @@ impl ResourceMonitor {
 
     /// Checks if monitoring is active
     pub fn is_monitoring(&self) -> bool {
-        *self.monitoring_active.read().unwrap()
+        self.monitoring_active.load(Ordering::Acquire)
+    }
+}
+
+impl Drop for ResourceMonitor {
+    fn drop(&mut self) {
+        self.stop_monitoring();
+    }
+}
+
+/// Sends `value` to every live subscriber, dropping any whose receiver is gone.
+fn broadcast<T: Clone>(subscribers: &Mutex<Vec<Sender<T>>>, value: &T) {
+    subscribers
+        .lock()
+        .unwrap()
+        .retain(|tx| tx.send(value.clone()).is_ok());
+}
+
+/// Evaluates all thresholds against a sample, returning the alerts that fired.
+fn evaluate_alerts(
+    metrics: &ResourceMetrics,
+    config: &MonitorConfig,
+    network_rate_bytes_per_sec: f64,
+) -> Vec<ResourceAlert> {
+    let now = Instant::now();
+    let mut alerts = Vec::new();
+
+    if metrics.cpu_usage_percent > config.cpu_threshold {
+        alerts.push(ResourceAlert {
+            alert_type: AlertType::HighCpuUsage,
+            metric_value: metrics.cpu_usage_percent,
+            threshold: config.cpu_threshold,
+            timestamp: now,
+        });
+    }
+
+    let memory_usage_percent =
+        (metrics.memory_usage_bytes as f64 / metrics.memory_total_bytes as f64) * 100.0;
+    if memory_usage_percent > config.memory_threshold {
+        alerts.push(ResourceAlert {
+            alert_type: AlertType::HighMemoryUsage,
+            metric_value: memory_usage_percent,
+            threshold: config.memory_threshold,
+            timestamp: now,
+        });
     }
+
+    let disk_usage_percent =
+        (metrics.disk_usage_bytes as f64 / metrics.disk_total_bytes as f64) * 100.0;
+    if disk_usage_percent > config.disk_threshold {
+        alerts.push(ResourceAlert {
+            alert_type: AlertType::LowDiskSpace,
+            metric_value: disk_usage_percent,
+            threshold: config.disk_threshold,
+            timestamp: now,
+        });
+    }
+
+    if network_rate_bytes_per_sec > config.network_threshold_bytes_per_sec {
+        alerts.push(ResourceAlert {
+            alert_type: AlertType::NetworkCongestion,
+            metric_value: network_rate_bytes_per_sec,
+            threshold: config.network_threshold_bytes_per_sec,
+            timestamp: now,
+        });
+    }
+
+    alerts
 }
 
 impl Default for MonitorConfig {
@@ -153,6 +363,7 @@ impl Default for MonitorConfig {
             cpu_threshold: 80.0,
             memory_threshold: 90.0,
             disk_threshold: 95.0,
+            network_threshold_bytes_per_sec: 100.0 * 1024.0 * 1024.0, // 100 MB/s
             sample_interval_ms: 1000,
         }
     }
@@ -162,11 +373,99 @@ impl Default for MonitorConfig {
 mod tests {
     use super::*;
 
+    /// Fixture source returning a fixed reading so alerts are deterministic.
+    struct FixedSource(ResourceSample);
+
+    impl MetricsSource for FixedSource {
+        fn sample(&self) -> ResourceSample {
+            self.0.clone()
+        }
+    }
+
+    fn hot_sample() -> ResourceSample {
+        ResourceSample {
+            cpu_usage_percent: 95.0,
+            memory_usage_bytes: 15,
+            memory_total_bytes: 16,
+            disk_usage_bytes: 99,
+            disk_total_bytes: 100,
+            network_rx_bytes: 0,
+            network_tx_bytes: 0,
+        }
+    }
+
     #[test]
     fn test_resource_monitor_creation() {
         let config = MonitorConfig::default();
-        let monitor = ResourceMonitor::new(config);
+        let monitor = ResourceMonitor::with_source(config, Arc::new(FixedSource(hot_sample())));
         assert!(!monitor.is_monitoring());
         assert!(monitor.get_alerts().is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_sampler_streams_metrics_and_alerts() {
+        let config = MonitorConfig {
+            sample_interval_ms: 5,
+            ..MonitorConfig::default()
+        };
+        let monitor = ResourceMonitor::with_source(config, Arc::new(FixedSource(hot_sample())));
+        let metrics_rx = monitor.subscribe();
+        let alerts_rx = monitor.subscribe_alerts();
+
+        monitor.start_monitoring();
+        let metric = metrics_rx.recv().unwrap();
+        assert_eq!(metric.cpu_usage_percent, 95.0);
+
+        let alert = alerts_rx.recv().unwrap();
+        assert!(matches!(
+            alert.alert_type,
+            AlertType::HighCpuUsage
+                | AlertType::HighMemoryUsage
+                | AlertType::LowDiskSpace
+        ));
+        monitor.stop_monitoring();
+        assert!(!monitor.is_monitoring());
+    }
+
+    #[test]
+    fn test_alerts_are_edge_triggered_and_bounded() {
+        let config = MonitorConfig {
+            sample_interval_ms: 1,
+            ..MonitorConfig::default()
+        };
+        let monitor = ResourceMonitor::with_source(config, Arc::new(FixedSource(hot_sample())));
+        monitor.start_monitoring();
+        // Many ticks elapse while the same thresholds stay breached.
+        thread::sleep(Duration::from_millis(50));
+        monitor.stop_monitoring();
+
+        let alerts = monitor.get_alerts();
+        // A sustained breach fires each alert type exactly once, not per tick.
+        let distinct: HashSet<_> = alerts.iter().map(|a| a.alert_type.clone()).collect();
+        assert_eq!(alerts.len(), distinct.len());
+        assert!(alerts.len() <= MAX_RETAINED_ALERTS);
+    }
+
+    #[test]
+    fn test_disk_and_network_thresholds_evaluated() {
+        let config = MonitorConfig {
+            network_threshold_bytes_per_sec: 10.0,
+            ..MonitorConfig::default()
+        };
+        let metrics = ResourceMetrics {
+            cpu_usage_percent: 1.0,
+            memory_usage_bytes: 1,
+            memory_total_bytes: 100,
+            disk_usage_bytes: 99,
+            disk_total_bytes: 100,
+            network_rx_bytes: 0,
+            network_tx_bytes: 0,
+            timestamp: Instant::now(),
+        };
+        let fired = evaluate_alerts(&metrics, &config, 1000.0);
+        assert!(fired.iter().any(|a| a.alert_type == AlertType::LowDiskSpace));
+        assert!(fired
+            .iter()
+            .any(|a| a.alert_type == AlertType::NetworkCongestion));
+    }
+}
@@ -1,6 +1,8 @@
-use std::sync::{Arc, Mutex};
-use std::thread;
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+
+use tokio::runtime::{self, Runtime};
+use tokio::task::{AbortHandle, JoinHandle};
 
 /// Status of a task execution
 #[derive(Debug, Clone, PartialEq)]
@@ -20,6 +22,14 @@ pub struct ExecutionResult {
     pub output: Option<Vec<u8>>,
     pub error: Option<String>,
     pub execution_time_ms: u64,
+    /// Total weight charged for the task: `base_cost` plus the measured
+    /// dynamic cost (elapsed wall-clock milliseconds and bytes processed;
+    /// CPU time is not measured).
+    pub consumed_weight: u64,
+    /// Whether `output` was truncated to respect `output_bytes_limit`.
+    pub truncated: bool,
+    /// Length in bytes of the output the task produced before truncation.
+    pub original_output_len: usize,
 }
 
 /// Configuration for task execution
@@ -28,74 +38,221 @@ pub struct ExecutionConfig {
     pub max_execution_time_ms: u64,
     pub retry_attempts: u32,
     pub resource_limit: u64,
+    /// Fixed weight charged for admitting any task, before dynamic cost.
+    pub base_cost: u64,
+    /// Maximum number of output bytes retained per task. `None` keeps the full
+    /// output; when set, excess bytes are dropped and a truncation marker is
+    /// appended.
+    pub output_bytes_limit: Option<usize>,
+}
+
+/// Reference to the tokio runtime work is spawned onto.
+///
+/// Production code holds a `Weak` reference to the shared runtime so the
+/// executor never keeps it alive, while async tests inject an already-running
+/// `runtime::Handle` directly — dropping an owned `Runtime` from within an
+/// async context panics, so the handle variant is required there.
+pub enum Handle {
+    /// Weak reference to a shared runtime, upgraded on demand.
+    Weak(Weak<Runtime>),
+    /// A live runtime handle, typically from an already-running runtime.
+    Owned(runtime::Handle),
+}
+
+impl Handle {
+    /// Resolves a usable runtime handle, or `None` if the runtime is gone.
+    fn resolve(&self) -> Option<runtime::Handle> {
+        match self {
+            Handle::Weak(weak) => weak.upgrade().map(|rt| rt.handle().clone()),
+            Handle::Owned(handle) => Some(handle.clone()),
+        }
+    }
 }
 
 /// TaskExecutor handles the execution of computational tasks
 pub struct TaskExecutor {
     config: ExecutionConfig,
-    active_tasks: Arc<Mutex<Vec<String>>>,
+    handle: Handle,
+    active_tasks: Arc<Mutex<HashMap<String, AbortHandle>>>,
     completed_count: Arc<Mutex<u64>>,
+    consumed_weight: Arc<Mutex<u64>>,
 }
 
 impl TaskExecutor {
-    /// Creates a new TaskExecutor instance
-    pub fn new(config: ExecutionConfig) -> Self {
+    /// Creates a new TaskExecutor instance bound to a runtime handle
+    pub fn new(config: ExecutionConfig, handle: Handle) -> Self {
         Self {
             config,
-            active_tasks: Arc::new(Mutex::new(Vec::new())),
+            handle,
+            active_tasks: Arc::new(Mutex::new(HashMap::new())),
             completed_count: Arc::new(Mutex::new(0)),
+            consumed_weight: Arc::new(Mutex::new(0)),
         }
     }
 
+    /// Spawns a future onto the executor's runtime.
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: std::future::Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.resolve_handle().spawn(future)
+    }
+
+    /// Spawns a blocking closure onto the runtime's blocking pool.
+    pub fn spawn_blocking_handle<F, R>(&self, f: F) -> JoinHandle<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        self.resolve_handle().spawn_blocking(f)
+    }
+
     /// Executes a task with the given payload
-    pub fn execute_task(&self, task_id: String, payload: Vec<u8>) -> ExecutionResult {
-        // Add task to active list
-        {
-            let mut active = self.active_tasks.lock().unwrap();
-            active.push(task_id.clone());
+    pub async fn execute_task(&self, task_id: String, payload: Vec<u8>) -> ExecutionResult {
+        // Reject up front if the fixed base cost alone would exceed the budget.
+        if self.config.base_cost > self.remaining_budget() {
+            return ExecutionResult {
+                task_id,
+                status: TaskStatus::Failed,
+                output: None,
+                error: Some("Resource limit exceeded".to_string()),
+                execution_time_ms: 0,
+                consumed_weight: 0,
+                truncated: false,
+                original_output_len: 0,
+            };
         }
 
+        let handle = match self.handle.resolve() {
+            Some(handle) => handle,
+            None => {
+                return ExecutionResult {
+                    task_id,
+                    status: TaskStatus::Failed,
+                    output: None,
+                    error: Some("Runtime handle unavailable".to_string()),
+                    execution_time_ms: 0,
+                    consumed_weight: 0,
+                    truncated: false,
+                    original_output_len: 0,
+                };
+            }
+        };
+
+        let id = task_id.clone();
+        let base_cost = self.config.base_cost;
+        let output_limit = self.config.output_bytes_limit;
+        let join = handle.spawn(async move { Self::run_task(id, payload, base_cost, output_limit) });
+        self.active_tasks
+            .lock()
+            .unwrap()
+            .insert(task_id.clone(), join.abort_handle());
+
+        let result = match join.await {
+            Ok(result) => result,
+            Err(err) if err.is_cancelled() => ExecutionResult {
+                task_id: task_id.clone(),
+                status: TaskStatus::Cancelled,
+                output: None,
+                error: None,
+                execution_time_ms: 0,
+                consumed_weight: 0,
+                truncated: false,
+                original_output_len: 0,
+            },
+            Err(err) => ExecutionResult {
+                task_id: task_id.clone(),
+                status: TaskStatus::Failed,
+                output: None,
+                error: Some(err.to_string()),
+                execution_time_ms: 0,
+                consumed_weight: 0,
+                truncated: false,
+                original_output_len: 0,
+            },
+        };
+
+        self.active_tasks.lock().unwrap().remove(&task_id);
+        *self.completed_count.lock().unwrap() += 1;
+        *self.consumed_weight.lock().unwrap() += result.consumed_weight;
+        result
+    }
+
+    /// Runs the task body and produces its result.
+    ///
+    /// The task is charged `base_cost` plus a dynamic cost measured from the
+    /// wall-clock milliseconds the task body takes (not CPU time) and the
+    /// number of payload bytes processed.
+    fn run_task(
+        task_id: String,
+        payload: Vec<u8>,
+        base_cost: u64,
+        output_limit: Option<usize>,
+    ) -> ExecutionResult {
         let start_time = std::time::Instant::now();
-        
-        // Simulate task execution (stub implementation)
-        thread::sleep(Duration::from_millis(100));
-        
+        let bytes_processed = payload.len() as u64;
+        let original_output_len = payload.len();
+        let (output, truncated) = Self::cap_output(payload, output_limit);
         let execution_time_ms = start_time.elapsed().as_millis() as u64;
-        
-        // Remove from active list and increment completed count
-        {
-            let mut active = self.active_tasks.lock().unwrap();
-            active.retain(|id| id != &task_id);
-            
-            let mut count = self.completed_count.lock().unwrap();
-            *count += 1;
-        }
-        
-        // Return stub result
+        let consumed_weight = base_cost + execution_time_ms + bytes_processed;
         ExecutionResult {
             task_id,
             status: TaskStatus::Completed,
-            output: Some(payload),
+            output: Some(output),
             error: None,
             execution_time_ms,
+            consumed_weight,
+            truncated,
+            original_output_len,
         }
     }
 
-    /// Cancels a running task
+    /// Trailing marker appended to output that was truncated.
+    const TRUNCATION_MARKER: &'static [u8] = b"...[truncated]";
+
+    /// Caps `output` to `limit` bytes, appending a truncation marker when the
+    /// limit is exceeded. Returns the (possibly shortened) bytes and whether
+    /// truncation occurred. `None` keeps the output unchanged.
+    fn cap_output(mut output: Vec<u8>, limit: Option<usize>) -> (Vec<u8>, bool) {
+        match limit {
+            Some(limit) if output.len() > limit => {
+                output.truncate(limit);
+                output.extend_from_slice(Self::TRUNCATION_MARKER);
+                (output, true)
+            }
+            _ => (output, false),
+        }
+    }
+
+    /// Weight still available before `resource_limit` is exhausted.
+    fn remaining_budget(&self) -> u64 {
+        self.config
+            .resource_limit
+            .saturating_sub(*self.consumed_weight.lock().unwrap())
+    }
+
+    /// Total weight charged across all completed tasks.
+    pub fn get_consumed_weight(&self) -> u64 {
+        *self.consumed_weight.lock().unwrap()
+    }
+
+    /// Cancels a running task, aborting its underlying join handle
     pub fn cancel_task(&self, task_id: &str) -> Result<(), String> {
         let mut active = self.active_tasks.lock().unwrap();
-        if let Some(pos) = active.iter().position(|id| id == task_id) {
-            active.remove(pos);
-            Ok(())
-        } else {
-            Err("Task not found".to_string())
+        match active.remove(task_id) {
+            Some(abort_handle) => {
+                abort_handle.abort();
+                Ok(())
+            }
+            None => Err("Task not found".to_string()),
         }
     }
 
     /// Gets the list of currently active tasks
     pub fn get_active_tasks(&self) -> Vec<String> {
         let active = self.active_tasks.lock().unwrap();
-        active.clone()
+        active.keys().cloned().collect()
     }
 
     /// Gets the number of completed tasks
@@ -104,9 +261,16 @@ impl TaskExecutor {
     }
 
     /// Checks if the executor can accept more tasks
+    ///
+    /// A task is admissible only if its fixed `base_cost` still fits within the
+    /// remaining resource budget.
     pub fn can_accept_task(&self) -> bool {
-        let active = self.active_tasks.lock().unwrap();
-        active.len() < 10 // Arbitrary limit for stub
+        self.config.base_cost <= self.remaining_budget()
+    }
+
+    /// Resolves the runtime handle, panicking if the runtime has been dropped.
+    fn resolve_handle(&self) -> runtime::Handle {
+        self.handle.resolve().expect("runtime handle unavailable")
     }
 }
 
@@ -116,6 +280,8 @@ impl Default for ExecutionConfig {
             max_execution_time_ms: 60000,
             retry_attempts: 3,
             resource_limit: 1024 * 1024 * 1024, // 1GB
+            base_cost: 1,
+            output_bytes_limit: None,
         }
     }
 }
@@ -126,9 +292,58 @@ mod tests {
 
     #[test]
     fn test_task_executor_creation() {
-        let config = ExecutionConfig::default();
-        let executor = TaskExecutor::new(config);
+        let rt = Runtime::new().unwrap();
+        let executor =
+            TaskExecutor::new(ExecutionConfig::default(), Handle::Owned(rt.handle().clone()));
         assert_eq!(executor.get_completed_count(), 0);
         assert!(executor.can_accept_task());
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_execute_task_echoes_payload() {
+        let handle = Handle::Owned(tokio::runtime::Handle::current());
+        let executor = TaskExecutor::new(ExecutionConfig::default(), handle);
+        let result = executor.execute_task("t1".to_string(), vec![1, 2, 3]).await;
+        assert_eq!(result.status, TaskStatus::Completed);
+        assert_eq!(result.output, Some(vec![1, 2, 3]));
+        assert_eq!(executor.get_completed_count(), 1);
+        // base_cost (1) + payload bytes (3) are charged even on a fast task.
+        assert!(result.consumed_weight >= 4);
+        assert_eq!(executor.get_consumed_weight(), result.consumed_weight);
+        assert!(!result.truncated);
+        assert_eq!(result.original_output_len, 3);
+    }
+
+    #[tokio::test]
+    async fn test_output_truncated_to_limit() {
+        let config = ExecutionConfig {
+            output_bytes_limit: Some(2),
+            ..ExecutionConfig::default()
+        };
+        let handle = Handle::Owned(tokio::runtime::Handle::current());
+        let executor = TaskExecutor::new(config, handle);
+        let result = executor
+            .execute_task("t1".to_string(), vec![1, 2, 3, 4, 5])
+            .await;
+        assert!(result.truncated);
+        assert_eq!(result.original_output_len, 5);
+        let output = result.output.unwrap();
+        assert_eq!(&output[..2], &[1, 2]);
+        assert!(output.ends_with(b"...[truncated]"));
+    }
+
+    #[tokio::test]
+    async fn test_base_cost_rejected_when_budget_exhausted() {
+        let config = ExecutionConfig {
+            resource_limit: 0,
+            base_cost: 5,
+            ..ExecutionConfig::default()
+        };
+        let handle = Handle::Owned(tokio::runtime::Handle::current());
+        let executor = TaskExecutor::new(config, handle);
+        assert!(!executor.can_accept_task());
+        let result = executor.execute_task("t1".to_string(), vec![1]).await;
+        assert_eq!(result.status, TaskStatus::Failed);
+        assert_eq!(result.consumed_weight, 0);
+    }
+}